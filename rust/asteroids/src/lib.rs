@@ -0,0 +1,25 @@
+//! Day 10's asteroid maps.
+
+use std::fmt;
+
+use geometry::{Dense, Point};
+
+/// An asteroid's position, shown the way day 10 has always printed it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Asteroid(pub Point);
+
+impl fmt::Display for Asteroid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Asteroid({}, {})", self.0.x, self.0.y)
+    }
+}
+
+/// Every `#` in the map, row by row.
+pub fn parse(map: &str) -> Result<Vec<Point>, String> {
+    let map = Dense::parse(map, |c| c == '#')?;
+    Ok(map
+        .iter()
+        .filter(|&(_, &asteroid)| asteroid)
+        .map(|(pos, _)| pos)
+        .collect())
+}
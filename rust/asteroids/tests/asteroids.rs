@@ -0,0 +1,24 @@
+use asteroids::{parse, Asteroid};
+use geometry::Point;
+
+#[test]
+fn parses_maps() {
+    assert_eq!(
+        parse(".#..#\n.....\n#####\n").unwrap(),
+        vec![
+            Point::new(1, 0),
+            Point::new(4, 0),
+            Point::new(0, 2),
+            Point::new(1, 2),
+            Point::new(2, 2),
+            Point::new(3, 2),
+            Point::new(4, 2),
+        ]
+    );
+    assert!(parse("#.\n#\n").is_err());
+}
+
+#[test]
+fn shows_asteroids() {
+    assert_eq!(Asteroid(Point::new(8, 16)).to_string(), "Asteroid(8, 16)");
+}
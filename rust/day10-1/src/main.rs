@@ -4,75 +4,52 @@ use std::io::prelude::*;
 
 use itertools::Itertools;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct Asteroid(i32, i32);
-
-impl Asteroid {
-    pub fn new(x: i32, y: i32) -> Self {
-        Asteroid(x, y)
-    }
-}
+use asteroids::Asteroid;
 
 fn main() {
-    let stdin = io::stdin();
-    let mut y: i32 = 0;
-    let mut asteroids = Vec::new();
-
-    for line in stdin.lock().lines() {
-        let line = line.unwrap();
-
-        for (x, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                asteroids.push(Asteroid::new(x as i32, y));
-            }
-        }
-
-        y += 1;
-    }
+    let mut map = String::new();
+    io::stdin().read_to_string(&mut map).unwrap();
+    let asteroids = asteroids::parse(&map).unwrap();
 
     let mut occlusions = HashSet::new();
 
     for pair in asteroids.iter().combinations(2) {
         let first = pair[0];
         let second = pair[1];
-        let vector = (second.0 - first.0, second.1 - first.1);
+        let vector = *second - *first;
 
         for other in asteroids.iter().filter(|a| *a != first && *a != second) {
-            let other_vector = (other.0 - first.0, other.1 - first.1);
+            let other_vector = *other - *first;
 
             // Exclude differences in sign.
-            if vector.0 * other_vector.0 < 0 {
-                continue;
-            } else if vector.1 * other_vector.1 < 0 {
+            if vector.x * other_vector.x < 0 || vector.y * other_vector.y < 0 {
                 continue;
             }
 
-            if vector.0 == 0 {
-                if other_vector.0 == 0 && other_vector.1 < vector.1 {
+            if vector.x == 0 {
+                if other_vector.x == 0 && other_vector.y < vector.y {
                     occlusions.insert((first, second));
                     occlusions.insert((second, first));
                 }
                 continue;
-            } else if other_vector.0 == 0 {
+            } else if other_vector.x == 0 {
                 continue;
             }
 
-            if vector.1 == 0 {
-                if other_vector.1 == 0 && other_vector.0 < vector.0 {
+            if vector.y == 0 {
+                if other_vector.y == 0 && other_vector.x < vector.x {
                     occlusions.insert((first, second));
                     occlusions.insert((second, first));
                 }
                 continue;
-            } else if other_vector.1 == 0 {
+            } else if other_vector.y == 0 {
                 continue;
             }
 
-            let x_multiple = f64::from(vector.0) / f64::from(other_vector.0);
-            let y_multiple = f64::from(vector.1) / f64::from(other_vector.1);
+            let x_multiple = vector.x as f64 / other_vector.x as f64;
+            let y_multiple = vector.y as f64 / other_vector.y as f64;
 
-            if x_multiple < 1.0 || y_multiple < 1.0 {
-                continue;
-            } else if x_multiple != y_multiple {
+            if x_multiple < 1.0 || y_multiple < 1.0 || x_multiple != y_multiple {
                 continue;
             }
 
@@ -97,5 +74,5 @@ fn main() {
         }
     }
 
-    println!("{:?} {}", site, max_visible);
+    println!("{} {}", Asteroid(*site), max_visible);
 }
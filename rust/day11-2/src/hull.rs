@@ -0,0 +1,290 @@
+//! Drawing the hull once the robot's done, as text or as an image.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use geometry::{Bounds, Heading, Point};
+use ocr::Bitmap;
+
+pub type Panels = HashSet<Point>;
+
+const BLACK: u8 = 0;
+const WHITE: u8 = 1;
+const ROBOT: u8 = 2;
+
+pub struct Hull<'a> {
+    white: &'a Panels,
+    bounds: Bounds,
+    width: usize,
+    height: usize,
+    robot: Option<(Point, Heading)>,
+}
+
+impl<'a> Hull<'a> {
+    /// Takes in every panel that was painted or is white, plus the robot
+    /// if it's to be marked.
+    pub fn new(white: &'a Panels, painted: &Panels, robot: Option<(Point, Heading)>) -> Self {
+        let panels = white
+            .iter()
+            .chain(painted.iter())
+            .chain(robot.iter().map(|(pos, _)| pos))
+            .cloned();
+        let bounds =
+            Bounds::around(panels).unwrap_or_else(|| Bounds::new(Point::ORIGIN, -Point::new(1, 1)));
+
+        Hull {
+            white,
+            bounds,
+            width: bounds.width(),
+            height: bounds.height(),
+            robot,
+        }
+    }
+
+    fn panel(&self, x: usize, y: usize) -> u8 {
+        let pos = self.bounds.min + Point::new(x as i64, y as i64);
+        if self.white.contains(&pos) {
+            WHITE
+        } else {
+            BLACK
+        }
+    }
+
+    /// The robot's position inside the box.
+    fn robot(&self) -> Option<(usize, usize, Heading)> {
+        self.robot.map(|(pos, heading)| {
+            let offset = pos - self.bounds.min;
+            (offset.x as usize, offset.y as usize, heading)
+        })
+    }
+
+    /// `#` for white and `.` for black, with the robot as an arrow.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = match self.robot() {
+                    Some((rx, ry, heading)) if (rx, ry) == (x, y) => heading.arrow(),
+                    _ if self.panel(x, y) == WHITE => '#',
+                    _ => '.',
+                };
+                text.push(c);
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// White panels as ink, for reading the registration identifier off.
+    /// It's framed around the white panels alone, so neither the robot
+    /// nor panels painted black can shift or pad the letters.
+    pub fn bitmap(&self) -> Bitmap {
+        match Bounds::around(self.white.iter().cloned()) {
+            Some(bounds) => Bitmap::new(bounds.width(), bounds.height(), |x, y| {
+                self.white
+                    .contains(&(bounds.min + Point::new(x as i64, y as i64)))
+            }),
+            None => Bitmap::new(0, 0, |_, _| false),
+        }
+    }
+
+    /// One byte per pixel, each panel `scale` pixels square. The robot is
+    /// a triangle pointing the way it faces.
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let width = self.width * scale;
+        let mut pixels = vec![BLACK; width * self.height * scale];
+
+        for y in 0..self.height * scale {
+            for x in 0..width {
+                pixels[y * width + x] = self.panel(x / scale, y / scale);
+            }
+        }
+
+        if let Some((rx, ry, heading)) = self.robot() {
+            let mid = (scale - 1) as f64 / 2.0;
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    // How far along the heading and how far off it.
+                    let (along, across) = match heading {
+                        Heading::Up => (dy, dx),
+                        Heading::Down => (scale - 1 - dy, dx),
+                        Heading::Left => (dx, dy),
+                        Heading::Right => (scale - 1 - dx, dy),
+                    };
+
+                    if (across as f64 - mid).abs() <= along as f64 / 2.0 {
+                        pixels[(ry * scale + dy) * width + rx * scale + dx] = ROBOT;
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+
+    /// A plain PBM, where black is 1. The robot is drawn in whichever
+    /// colour stands out from its panel.
+    pub fn pbm(&self, scale: usize) -> String {
+        let width = self.width * scale;
+        let robot = self.robot().map(|(x, y, _)| self.panel(x, y));
+        let mut pbm = format!("P1\n{} {}\n", width, self.height * scale);
+
+        for row in self.pixels(scale).chunks(width) {
+            let bits: Vec<&str> = row
+                .iter()
+                .map(|&p| match p {
+                    WHITE => "0",
+                    ROBOT if robot == Some(BLACK) => "0",
+                    ROBOT => "1",
+                    _ => "1",
+                })
+                .collect();
+            writeln!(pbm, "{}", bits.join(" ")).unwrap();
+        }
+
+        pbm
+    }
+
+    pub fn png<W: Write>(&self, scale: usize, output: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(
+            output,
+            (self.width * scale) as u32,
+            (self.height * scale) as u32,
+        );
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(vec![0, 0, 0, 255, 255, 255, 220, 40, 40]);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels(scale))
+            .map_err(io::Error::other)
+    }
+
+    /// A black background with a square for each white panel.
+    pub fn svg(&self, scale: usize) -> String {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            width, height
+        );
+        writeln!(
+            svg,
+            "  <rect width=\"{}\" height=\"{}\" fill=\"black\"/>",
+            width, height
+        )
+        .unwrap();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.panel(x, y) == WHITE {
+                    writeln!(
+                        svg,
+                        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>",
+                        x * scale,
+                        y * scale,
+                        scale,
+                        scale
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        if let Some((x, y, heading)) = self.robot() {
+            let (x, y, s) = ((x * scale) as f64, (y * scale) as f64, scale as f64);
+            let points = match heading {
+                Heading::Up => [(x + s / 2.0, y), (x + s, y + s), (x, y + s)],
+                Heading::Right => [(x + s, y + s / 2.0), (x, y + s), (x, y)],
+                Heading::Down => [(x + s / 2.0, y + s), (x, y), (x + s, y)],
+                Heading::Left => [(x, y + s / 2.0), (x + s, y), (x + s, y + s)],
+            };
+            let points: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+            writeln!(
+                svg,
+                "  <polygon points=\"{}\" fill=\"red\"/>",
+                points.join(" ")
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panels() -> (Panels, Panels) {
+        let white: Panels = vec![(-1, 2), (1, 2), (0, 3)]
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        let mut painted = white.clone();
+        painted.insert(Point::new(1, 4));
+        (white, painted)
+    }
+
+    #[test]
+    fn text_covers_every_painted_panel() {
+        let (white, painted) = panels();
+        let hull = Hull::new(&white, &painted, None);
+        assert_eq!(hull.text(), "#.#\n.#.\n...\n");
+
+        let hull = Hull::new(&white, &painted, Some((Point::new(2, 3), Heading::Left)));
+        assert_eq!(hull.text(), "#.#.\n.#.<\n....\n");
+    }
+
+    #[test]
+    fn bitmap_leaves_the_robot_out() {
+        let (white, painted) = panels();
+        let hull = Hull::new(&white, &painted, Some((Point::new(6, -3), Heading::Up)));
+        let bitmap = hull.bitmap();
+        assert_eq!((bitmap.width(), bitmap.height()), (3, 2));
+        assert!(bitmap.get(0, 0));
+        assert!(bitmap.get(1, 1));
+        assert!(!bitmap.get(1, 0));
+
+        let none = Panels::new();
+        assert_eq!(Hull::new(&none, &painted, None).bitmap().width(), 0);
+    }
+
+    #[test]
+    fn pbm() {
+        let (white, painted) = panels();
+        let hull = Hull::new(&white, &painted, None);
+        assert_eq!(hull.pbm(1), "P1\n3 3\n0 1 0\n1 0 1\n1 1 1\n");
+        assert!(hull
+            .pbm(2)
+            .starts_with("P1\n6 6\n0 0 1 1 0 0\n0 0 1 1 0 0\n"));
+    }
+
+    #[test]
+    fn robot_points_its_heading() {
+        let white = Panels::new();
+        let painted: Panels = vec![Point::ORIGIN].into_iter().collect();
+        let hull = Hull::new(&white, &painted, Some((Point::ORIGIN, Heading::Right)));
+
+        let rows: Vec<Vec<u8>> = hull.pixels(5).chunks(5).map(|r| r.to_vec()).collect();
+        assert_eq!(rows[0], vec![ROBOT, 0, 0, 0, 0]);
+        assert_eq!(rows[1], vec![ROBOT, ROBOT, ROBOT, 0, 0]);
+        assert_eq!(rows[2], vec![ROBOT, ROBOT, ROBOT, ROBOT, ROBOT]);
+        assert_eq!(rows[4], vec![ROBOT, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn svg_has_a_square_per_white_panel() {
+        let (white, painted) = panels();
+        let svg = Hull::new(&white, &painted, Some((Point::new(1, 4), Heading::Up))).svg(10);
+
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"30\">"));
+        assert_eq!(svg.matches("fill=\"white\"").count(), 3);
+        assert!(svg.contains("<polygon points=\"25,20 30,30 20,30\" fill=\"red\"/>"));
+    }
+}
@@ -1,266 +1,108 @@
-use std::collections::HashSet;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io::BufReader;
+use std::process;
 
-type Addr = u32;
-type Cell = i64;
+use intcode::robot::{Command, Decoder, PaintTurn, Robot};
+use intcode::{session, Cell, Device, Intcode};
 
-struct Intcode {
-    memory: Vec<Cell>,
-    pc: Addr,
-    relative_base: Cell,
-    robot: Robot,
-    output_buffer: Option<Cell>,
-}
-
-impl Intcode {
-    pub fn new(contents: &Vec<Cell>) -> Self {
-        Intcode {
-            memory: contents.clone(),
-            pc: 0,
-            relative_base: 0,
-            robot: Robot::new(),
-            output_buffer: None,
-        }
-    }
-
-    fn get_op(&self) -> (u32, Modes) {
-        let mut op = self.memory[self.pc as usize];
-        let mut modes = Vec::new();
-
-        let opcode = op % 100;
-        op /= 100;
-
-        while op != 0 {
-            modes.push((op % 10) as u32);
-            op /= 10;
-        }
-
-        (opcode as u32, Modes::new(modes))
-    }
-
-    fn addr(&mut self, addr: Addr, mode: u32) -> usize {
-        let addr = match mode {
-            0 => self.memory[addr as usize] as usize,
-            1 => addr as usize,
-            2 => (self.memory[addr as usize] + self.relative_base) as usize,
-            x => panic!("unknown addressing mode: {}", x),
-        };
-
-        while self.memory.len() <= addr {
-            self.memory.push(0);
-        }
-
-        addr
-    }
-
-    fn get(&mut self, addr: Addr, mode: u32) -> Cell {
-        let addr = self.addr(addr, mode);
-        self.memory[addr]
-    }
-
-    fn set(&mut self, addr: Addr, mode: u32, value: Cell) {
-        let addr = self.addr(addr, mode);
-        self.memory[addr] = value;
-    }
-
-    fn op_3<F>(&mut self, mut modes: Modes, op: F)
-    where
-        F: Fn(Cell, Cell) -> Cell,
-    {
-        modes.ensure(3);
-
-        let arg1 = self.get(self.pc + 1, modes.0[0]);
-        let arg2 = self.get(self.pc + 2, modes.0[1]);
-        self.set(self.pc + 3, modes.0[2], op(arg1, arg2));
-        self.pc += 4;
-    }
-
-    fn conditional_jump<F>(&mut self, mut modes: Modes, op: F)
-    where
-        F: Fn(Cell) -> bool,
-    {
-        modes.ensure(2);
-        if op(self.get(self.pc + 1, modes.0[0])) {
-            self.pc = self.get(self.pc + 2, modes.0[1]) as Addr;
-        } else {
-            self.pc += 3;
-        }
-    }
-
-    fn read_stdin(&mut self, mut modes: Modes) {
-        modes.ensure(1);
-        let value = self.robot.look();
-        self.set(self.pc + 1, modes.0[0], value);
-        self.pc += 2;
-    }
-
-    fn write_stdout(&mut self, mut modes: Modes) {
-        modes.ensure(1);
-        let value = self.get(self.pc + 1, modes.0[0]);
-        match self.output_buffer {
-            Some(v1) => {
-                self.robot.command(v1, value);
-                self.output_buffer = None;
-            }
-            None => self.output_buffer = Some(value),
-        };
-        self.pc += 2;
-    }
-
-    fn set_relative_base(&mut self, mut modes: Modes) {
-        modes.ensure(1);
-        self.relative_base += self.get(self.pc + 1, modes.0[0]);
-        self.pc += 2;
-    }
-
-    pub fn run(&mut self) {
-        loop {
-            let (opcode, modes) = self.get_op();
-            match opcode {
-                1 => self.op_3(modes, |a, b| a + b),
-                2 => self.op_3(modes, |a, b| a * b),
-                3 => self.read_stdin(modes),
-                4 => self.write_stdout(modes),
-                5 => self.conditional_jump(modes, |v| v != 0),
-                6 => self.conditional_jump(modes, |v| v == 0),
-                7 => self.op_3(modes, |a, b| if a < b { 1 } else { 0 }),
-                8 => self.op_3(modes, |a, b| if a == b { 1 } else { 0 }),
-                9 => self.set_relative_base(modes),
-                99 => break,
-                x => panic!("unknown opcode: {}", x),
-            };
-        }
-    }
-
-    #[allow(dead_code)]
-    pub fn dump(&self) {
-        let contents: Vec<String> = self.memory.iter().map(|e| e.to_string()).collect();
-        println!("{}", contents.join(","));
-    }
-}
-
-#[derive(Debug)]
-struct Modes(pub Vec<u32>);
+mod hull;
+mod path;
 
-impl Modes {
-    pub fn new(values: Vec<u32>) -> Self {
-        Modes(values)
-    }
-
-    pub fn ensure(&mut self, size: usize) {
-        while self.0.len() < size {
-            self.0.push(0);
-        }
-    }
-}
+use crate::hull::{Hull, Panels};
+use crate::path::Step;
 
 enum Colour {
     Black,
     White,
 }
 
-impl From<Cell> for Colour {
-    fn from(value: Cell) -> Self {
-        match value {
-            0 => Colour::Black,
-            1 => Colour::White,
-            x => panic!("invalid colour value: {}", x),
+impl Colour {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "black" => Some(Colour::Black),
+            "white" => Some(Colour::White),
+            _ => None,
         }
     }
 }
 
-impl Into<Cell> for Colour {
-    fn into(self) -> Cell {
-        match self {
+impl From<Colour> for Cell {
+    fn from(colour: Colour) -> Self {
+        match colour {
             Colour::Black => 0,
             Colour::White => 1,
         }
     }
 }
 
-enum Orientation {
-    Up,
-    Right,
-    Down,
-    Left,
+/// The shared robot, with each panel it paints kept as a step along its
+/// path.
+struct Painter {
+    robot: Robot<PaintTurn>,
+    seen: Option<Cell>,
+    path: Vec<Step>,
 }
 
-impl Orientation {
-    pub fn turn(&self, turn: Orientation) -> Self {
-        match (self, turn) {
-            (Orientation::Up, Orientation::Left) => Orientation::Left,
-            (Orientation::Right, Orientation::Left) => Orientation::Up,
-            (Orientation::Down, Orientation::Left) => Orientation::Right,
-            (Orientation::Left, Orientation::Left) => Orientation::Down,
-
-            (Orientation::Up, Orientation::Right) => Orientation::Right,
-            (Orientation::Right, Orientation::Right) => Orientation::Down,
-            (Orientation::Down, Orientation::Right) => Orientation::Left,
-            (Orientation::Left, Orientation::Right) => Orientation::Up,
+impl Painter {
+    pub fn new(start: Colour) -> Self {
+        let mut robot = Robot::new(PaintTurn::default());
+        robot.grid.insert(robot.pos, start.into());
 
-            _ => panic!("invalid turn"),
+        Painter {
+            robot,
+            seen: None,
+            path: Vec::new(),
         }
     }
-}
 
-impl From<Cell> for Orientation {
-    fn from(value: Cell) -> Self {
-        match value {
-            0 => Orientation::Left,
-            1 => Orientation::Right,
-            x => panic!("invalid turn orientation: {}", x),
-        }
+    /// Every panel that ended up white.
+    pub fn white(&self) -> Panels {
+        self.robot
+            .grid
+            .iter()
+            .filter(|&(_, &colour)| colour == Cell::from(Colour::White))
+            .map(|(pos, _)| pos)
+            .collect()
     }
-}
-
-struct Robot {
-    orientation: Orientation,
-    x: i32,
-    y: i32,
-    painted: HashSet<(i32, i32)>,
-    white: HashSet<(i32, i32)>,
-}
 
-impl Robot {
-    pub fn new() -> Self {
-        let mut robot = Robot {
-            orientation: Orientation::Up,
-            x: 0,
-            y: 0,
-            painted: HashSet::new(),
-            white: HashSet::new(),
-        };
-
-        robot.white.insert((0, 0));
-        robot
+    pub fn painted(&self) -> Panels {
+        self.path.iter().map(|step| step.pos).collect()
     }
+}
 
-    pub fn look(&self) -> Cell {
-        if self.white.contains(&(self.x, self.y)) {
-            Colour::White.into()
-        } else {
-            Colour::Black.into()
+impl Device for Painter {
+    fn read(&mut self) -> Cell {
+        let colour = self.robot.read();
+        self.seen = Some(colour);
+        colour
+    }
+
+    fn write(&mut self, value: Cell) {
+        for command in self.robot.decoder.decode(value) {
+            self.robot.apply(command);
+            match command {
+                Command::Paint(colour) => self.path.push(Step {
+                    pos: self.robot.pos,
+                    heading: self.robot.heading,
+                    read: self.seen.take(),
+                    wrote: colour,
+                }),
+                // The step records the way the robot leaves the panel.
+                Command::Turn(_) => {
+                    if let Some(step) = self.path.last_mut() {
+                        step.heading = self.robot.heading;
+                    }
+                }
+                _ => (),
+            }
         }
     }
+}
 
-    pub fn command(&mut self, colour: Cell, turn: Cell) {
-        match Colour::from(colour) {
-            Colour::Black => self.white.remove(&(self.x, self.y)),
-            Colour::White => self.white.insert((self.x, self.y)),
-        };
-
-        self.painted.insert((self.x, self.y));
-        self.orientation = self.orientation.turn(Orientation::from(turn));
-
-        match self.orientation {
-            Orientation::Up => self.y -= 1,
-            Orientation::Right => self.x += 1,
-            Orientation::Down => self.y += 1,
-            Orientation::Left => self.x -= 1,
-        };
-    }
+fn usage() -> ! {
+    eprintln!("usage: day11-2 PROGRAM [--start black|white] [--format letters|text|pbm|svg|png]\n               [--output FILE] [--scale N] [--mark] [--stats] [--csv FILE]\n               [--panels-csv FILE] [--record FILE | --replay FILE]");
+    process::exit(2);
 }
 
 fn main() -> std::io::Result<()> {
@@ -271,24 +113,104 @@ fn main() -> std::io::Result<()> {
     let mut data = String::new();
     buf_reader.read_to_string(&mut data)?;
 
-    let memory: Vec<Cell> = data
-        .trim()
-        .split(',')
-        .map(|e| Cell::from_str_radix(e, 10).unwrap())
-        .collect();
+    let memory = intcode::parse(&data);
+
+    let mut start = Colour::White;
+    let mut record = None;
+    let mut replay = None;
+    let mut format = String::from("letters");
+    let mut output = None;
+    let mut scale = 1;
+    let mut mark = false;
+    let mut stats = false;
+    let mut csv = None;
+    let mut panels_csv = None;
+
+    let mut args = std::env::args().skip(2);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--start" => {
+                let colour = args.next().unwrap();
+                start =
+                    Colour::parse(&colour).unwrap_or_else(|| panic!("unknown colour: {}", colour));
+            }
+            "--format" => format = args.next().unwrap(),
+            "--output" => output = Some(args.next().unwrap()),
+            "--scale" => {
+                scale = match args.next().map(|n| n.parse()) {
+                    Some(Ok(n)) if n >= 1 => n,
+                    _ => {
+                        eprintln!("--scale needs a whole number, at least 1");
+                        usage();
+                    }
+                }
+            }
+            "--mark" => mark = true,
+            "--stats" => stats = true,
+            "--csv" => csv = Some(args.next().unwrap()),
+            "--panels-csv" => panels_csv = Some(args.next().unwrap()),
+            "--record" | "--replay" if record.is_some() || replay.is_some() => usage(),
+            "--record" => record = Some(args.next().unwrap()),
+            "--replay" => replay = Some(args.next().unwrap()),
+            x => panic!("unknown option: {}", x),
+        }
+    }
+
+    let mut cpu = Intcode::new(&memory, Painter::new(start));
+
+    if let Some(path) = record {
+        cpu.record(session::create(path)?);
+    }
+
+    if let Some(path) = replay {
+        cpu.replay(session::load(path)?);
+    }
 
-    let mut cpu = Intcode::new(&memory);
     cpu.run();
 
-    for y in 0..6 {
-        for x in 0..43 {
-            if cpu.robot.white.contains(&(x, y)) {
-                print!("#");
-            } else {
-                print!(".");
+    let painter = &cpu.device;
+
+    if stats {
+        eprint!("{}", path::summary(&painter.path));
+    }
+
+    if let Some(file) = csv {
+        path::csv(&painter.path, File::create(file)?)?;
+    }
+
+    if let Some(file) = panels_csv {
+        path::panels_csv(&painter.path, File::create(file)?)?;
+    }
+    let marker = if mark {
+        Some((painter.robot.pos, painter.robot.heading))
+    } else {
+        None
+    };
+    let white = painter.white();
+    let hull = Hull::new(&white, &painter.painted(), marker);
+
+    let image = match format.as_str() {
+        "letters" => match ocr::read(&hull.bitmap()) {
+            Ok(letters) => format!("{}\n", letters).into_bytes(),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
             }
+        },
+        "text" => hull.text().into_bytes(),
+        "pbm" => hull.pbm(scale).into_bytes(),
+        "svg" => hull.svg(scale).into_bytes(),
+        "png" => {
+            let mut png = Vec::new();
+            hull.png(scale, &mut png)?;
+            png
         }
-        println!("");
+        x => panic!("unknown format: {}", x),
+    };
+
+    match output {
+        Some(path) => fs::write(path, image)?,
+        None => std::io::stdout().write_all(&image)?,
     }
 
     Ok(())
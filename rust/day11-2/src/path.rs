@@ -0,0 +1,206 @@
+//! The robot's path, one step per panel painted, and what it says about
+//! how the hull got painted.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use geometry::{Heading, Point};
+use intcode::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub pos: Point,
+    /// The way the robot moved off the panel.
+    pub heading: Heading,
+    /// What the camera saw, if the program looked.
+    pub read: Option<Cell>,
+    pub wrote: Cell,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Panel {
+    pub paints: usize,
+    /// The step that painted it last.
+    pub last: usize,
+    pub colour: Cell,
+}
+
+/// Every panel painted, in no particular order.
+pub fn panels(path: &[Step]) -> HashMap<Point, Panel> {
+    let mut panels: HashMap<Point, Panel> = HashMap::new();
+
+    for (n, step) in path.iter().enumerate() {
+        let panel = panels.entry(step.pos).or_default();
+        panel.paints += 1;
+        panel.last = n;
+        panel.colour = step.wrote;
+    }
+
+    panels
+}
+
+/// The longest stretch of steps moving the same way, as the step it
+/// starts at and how many steps it lasts.
+pub fn longest_run(path: &[Step]) -> Option<(usize, usize)> {
+    let mut longest: Option<(usize, usize)> = None;
+    let mut start = 0;
+
+    for n in 1..=path.len() {
+        if n == path.len() || path[n].heading != path[start].heading {
+            if longest.is_none_or(|(_, len)| n - start > len) {
+                longest = Some((start, n - start));
+            }
+            start = n;
+        }
+    }
+
+    longest
+}
+
+pub fn summary(path: &[Step]) -> String {
+    let panels = panels(path);
+    let repainted = panels.values().filter(|p| p.paints > 1).count();
+    let mut summary = String::new();
+
+    writeln!(summary, "steps {}", path.len()).unwrap();
+    writeln!(summary, "panels painted {}", panels.len()).unwrap();
+    writeln!(summary, "panels repainted {}", repainted).unwrap();
+
+    let most = panels
+        .iter()
+        .max_by_key(|&(pos, p)| (p.paints, -pos.y, -pos.x));
+    if let Some((pos, panel)) = most {
+        writeln!(summary, "most paints {} at {}", panel.paints, pos).unwrap();
+    }
+
+    if let Some((start, len)) = longest_run(path) {
+        let step = &path[start];
+        writeln!(
+            summary,
+            "longest straight run {} {} from {} at step {}",
+            len,
+            step.heading.name(),
+            step.pos,
+            start
+        )
+        .unwrap();
+    }
+
+    summary
+}
+
+pub fn csv<W: Write>(path: &[Step], mut output: W) -> io::Result<()> {
+    writeln!(output, "step,x,y,heading,read,wrote")?;
+
+    for (n, step) in path.iter().enumerate() {
+        let read = step.read.map(|r| r.to_string()).unwrap_or_default();
+        writeln!(
+            output,
+            "{},{},{},{},{}",
+            n,
+            step.pos,
+            step.heading.name(),
+            read,
+            step.wrote
+        )?;
+    }
+
+    output.flush()
+}
+
+/// One row per panel, top to bottom and left to right.
+pub fn panels_csv<W: Write>(path: &[Step], mut output: W) -> io::Result<()> {
+    let panels = panels(path);
+    let mut positions: Vec<&Point> = panels.keys().collect();
+    positions.sort_by_key(|pos| (pos.y, pos.x));
+
+    writeln!(output, "x,y,paints,last_step,colour")?;
+    for pos in positions {
+        let panel = &panels[pos];
+        writeln!(
+            output,
+            "{},{},{},{}",
+            pos, panel.paints, panel.last, panel.colour
+        )?;
+    }
+
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: i64, y: i64, heading: Heading, wrote: Cell) -> Step {
+        Step {
+            pos: Point::new(x, y),
+            heading,
+            read: Some(0),
+            wrote,
+        }
+    }
+
+    fn square() -> Vec<Step> {
+        vec![
+            step(0, 0, Heading::Left, 1),
+            step(-1, 0, Heading::Down, 0),
+            step(-1, 1, Heading::Right, 1),
+            step(0, 1, Heading::Up, 1),
+            step(0, 0, Heading::Left, 0),
+            step(-1, 0, Heading::Left, 1),
+            step(-2, 0, Heading::Left, 1),
+        ]
+    }
+
+    #[test]
+    fn panels_count_paints() {
+        let panels = panels(&square());
+        assert_eq!(panels.len(), 5);
+        assert_eq!(
+            panels[&Point::ORIGIN],
+            Panel {
+                paints: 2,
+                last: 4,
+                colour: 0
+            }
+        );
+        assert_eq!(panels[&Point::new(-1, 1)].paints, 1);
+    }
+
+    #[test]
+    fn longest_run_finds_the_last_stretch() {
+        assert_eq!(longest_run(&square()), Some((4, 3)));
+        assert_eq!(longest_run(&square()[..3]), Some((0, 1)));
+        assert_eq!(longest_run(&[]), None);
+    }
+
+    #[test]
+    fn summary() {
+        assert_eq!(
+            super::summary(&square()),
+            "steps 7\n\
+             panels painted 5\n\
+             panels repainted 2\n\
+             most paints 2 at -1,0\n\
+             longest straight run 3 left from 0,0 at step 4\n"
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let mut output = Vec::new();
+        super::csv(&square()[..2], &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "step,x,y,heading,read,wrote\n0,0,0,left,0,1\n1,-1,0,down,0,0\n"
+        );
+
+        let mut output = Vec::new();
+        panels_csv(&square()[..3], &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "x,y,paints,last_step,colour\n-1,0,1,1,0\n0,0,1,0,1\n-1,1,1,2,1\n"
+        );
+    }
+}
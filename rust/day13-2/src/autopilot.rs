@@ -0,0 +1,104 @@
+use geometry::{Point, Vector};
+use intcode::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Follows the ball's current x.
+    Track,
+    /// Heads for where the ball will come down.
+    Predict,
+}
+
+/// What the autopilot can see of the game.
+pub struct View {
+    pub ball: Point,
+    pub velocity: Option<Vector>,
+    pub paddle: Point,
+    /// The left and right walls.
+    pub walls: (Cell, Cell),
+    /// The lowest row with a block in it.
+    pub floor: Cell,
+}
+
+pub fn joystick(policy: Policy, view: &View) -> Cell {
+    let target = match policy {
+        // Blocks can send the ball anywhere, so wait until it's past them.
+        Policy::Predict if view.ball.y > view.floor => landing(view),
+        _ => None,
+    };
+
+    (target.unwrap_or(view.ball.x) - view.paddle.x).signum()
+}
+
+/// Where the ball will be when it reaches the row above the paddle,
+/// bouncing off the walls on the way, or nothing if it isn't on its way
+/// down. Blocks it might hit are ignored.
+pub fn landing(view: &View) -> Option<Cell> {
+    let Point { mut x, y } = view.ball;
+    let (left, right) = (view.walls.0 + 1, view.walls.1 - 1);
+    let Point { x: mut dx, y: dy } = view.velocity.filter(|v| v.y > 0)?;
+
+    let steps = (view.paddle.y - 1 - y) / dy;
+    for _ in 0..steps {
+        if x + dx < left || x + dx > right {
+            dx = -dx;
+        }
+        x += dx;
+    }
+
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(ball: (Cell, Cell), velocity: (Cell, Cell)) -> View {
+        View {
+            ball: ball.into(),
+            velocity: Some(velocity.into()),
+            paddle: Point::new(20, 22),
+            walls: (0, 40),
+            floor: 12,
+        }
+    }
+
+    #[test]
+    fn straight_down() {
+        assert_eq!(landing(&view((10, 15), (1, 1))), Some(16));
+        assert_eq!(landing(&view((10, 15), (-1, 1))), Some(4));
+        assert_eq!(landing(&view((10, 21), (1, 1))), Some(10));
+    }
+
+    #[test]
+    fn off_the_walls() {
+        assert_eq!(landing(&view((3, 15), (-1, 1))), Some(5));
+        assert_eq!(landing(&view((37, 15), (1, 1))), Some(35));
+    }
+
+    #[test]
+    fn going_up_tracks_the_ball() {
+        assert_eq!(landing(&view((10, 15), (1, -1))), None);
+        assert_eq!(joystick(Policy::Predict, &view((10, 15), (1, -1))), -1);
+    }
+
+    #[test]
+    fn above_the_blocks_tracks_the_ball() {
+        assert_eq!(joystick(Policy::Predict, &view((30, 10), (-1, 1))), 1);
+    }
+
+    #[test]
+    fn predict_heads_for_the_landing() {
+        // Comes down at 24, right of the paddle.
+        assert_eq!(joystick(Policy::Predict, &view((30, 15), (-1, 1))), 1);
+        // Comes down at 16, left of the paddle, though the ball is level
+        // with it now.
+        assert_eq!(joystick(Policy::Predict, &view((20, 17), (-1, 1))), -1);
+    }
+
+    #[test]
+    fn track_follows_the_ball() {
+        assert_eq!(joystick(Policy::Track, &view((30, 15), (-1, 1))), 1);
+        assert_eq!(joystick(Policy::Track, &view((20, 17), (-1, 1))), 0);
+    }
+}
@@ -1,251 +1,423 @@
-use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
+use std::io::{self, BufReader};
+use std::time::{Duration, Instant};
 
-use pancurses::{endwin, initscr, noecho, Window};
+use geometry::Point;
+use intcode::screen::{Screen, Update};
+use intcode::{patch, session, Cell, Device, Intcode};
 
-type Addr = u32;
-type Cell = i32;
+mod autopilot;
+mod record;
+mod render;
 
-struct Intcode {
-    memory: Vec<Cell>,
-    pc: Addr,
-    relative_base: Cell,
-    game: Game,
+use crate::autopilot::{Policy, View};
+use crate::record::Recorder;
+use crate::render::{Curses, Headless, Key, Renderer, Text};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Control {
+    Autopilot,
+    Manual,
+}
+
+struct Game {
+    screen: Screen,
+    renderer: Box<dyn Renderer>,
+    ball: Point,
+    last_ball: Option<Point>,
+    paddle: Point,
+    control: Control,
+    policy: Policy,
+    tick: Duration,
+    paused: bool,
+    quit: bool,
+    /// Blocks broken after each input, so `broken[0]` counts the blocks
+    /// broken before the first.
+    broken: Vec<u32>,
+    /// Inputs that moved the paddle.
+    moves: usize,
+    recorder: Option<Recorder>,
 }
 
-impl Intcode {
-    pub fn new(contents: &Vec<Cell>) -> Self {
-        Intcode {
-            memory: contents.clone(),
-            pc: 0,
-            relative_base: 0,
-            game: Game::new(),
+impl Game {
+    pub fn new(renderer: Box<dyn Renderer>, control: Control, tick: Duration) -> Self {
+        Game {
+            screen: Screen::new().register(-1, 0).palette(&render::TILES),
+            renderer,
+            ball: Point::ORIGIN,
+            last_ball: None,
+            paddle: Point::ORIGIN,
+            control,
+            policy: Policy::Predict,
+            tick,
+            paused: false,
+            quit: false,
+            broken: vec![0],
+            moves: 0,
+            recorder: None,
         }
     }
 
-    fn get_op(&self) -> (u32, Modes) {
-        let mut op = self.memory[self.pc as usize];
-        let mut modes = Vec::new();
+    fn autopilot(&self) -> Cell {
+        let view = View {
+            ball: self.ball,
+            velocity: self.last_ball.map(|last| self.ball - last),
+            paddle: self.paddle,
+            walls: self
+                .screen
+                .bounds()
+                .map_or((0, 0), |bounds| (bounds.min.x, bounds.max.x)),
+            floor: self
+                .screen
+                .tiles()
+                .iter()
+                .filter(|&(_, &tile)| tile == 2)
+                .map(|(pos, _)| pos.y)
+                .max()
+                .unwrap_or(0),
+        };
 
-        let opcode = op % 100;
-        op /= 100;
+        autopilot::joystick(self.policy, &view)
+    }
 
-        while op != 0 {
-            modes.push((op % 10) as u32);
-            op /= 10;
-        }
+    pub fn inputs(&self) -> usize {
+        self.broken.len() - 1
+    }
 
-        (opcode as u32, Modes::new(modes))
+    pub fn stats(&self) -> String {
+        let blocks: u32 = self.broken.iter().sum();
+        let busy = self.broken.iter().filter(|&&n| n > 0).count();
+        let most = self.broken.iter().cloned().max().unwrap_or(0);
+
+        let mut stats = String::new();
+        writeln!(stats, "inputs {}", self.inputs()).unwrap();
+        writeln!(stats, "blocks broken {}", blocks).unwrap();
+        writeln!(
+            stats,
+            "blocks per input {:.3}",
+            blocks as f64 / self.inputs().max(1) as f64
+        )
+        .unwrap();
+        writeln!(stats, "inputs that broke blocks {}", busy).unwrap();
+        writeln!(stats, "most blocks from one input {}", most).unwrap();
+        writeln!(stats, "paddle moves {}", self.moves).unwrap();
+        stats
     }
 
-    fn addr(&mut self, addr: Addr, mode: u32) -> usize {
-        let addr = match mode {
-            0 => self.memory[addr as usize] as usize,
-            1 => addr as usize,
-            2 => (self.memory[addr as usize] + self.relative_base) as usize,
-            x => panic!("unknown addressing mode: {}", x),
+    fn show_status(&mut self) {
+        let status = match (self.paused, self.control) {
+            (true, _) => "paused",
+            (false, Control::Autopilot) => "autopilot",
+            (false, Control::Manual) => "manual",
         };
+        self.renderer.status(status);
+    }
+
+    /// Handles keys until the tick is up and returns the joystick position
+    /// the player asked for, if they did.
+    fn controls(&mut self) -> Option<Cell> {
+        let deadline = Instant::now() + self.tick;
+        let mut joystick = None;
+
+        loop {
+            let now = Instant::now();
+            let wait = if self.paused {
+                self.tick
+            } else if now < deadline {
+                deadline - now
+            } else {
+                break;
+            };
+
+            let key = match self.renderer.key(wait) {
+                Some(key) => key,
+                None if self.paused => continue,
+                None => break,
+            };
+
+            match key {
+                Key::Left => joystick = Some(-1),
+                Key::Right => joystick = Some(1),
+                Key::Pause => self.paused = !self.paused,
+                Key::Autopilot => {
+                    self.control = match self.control {
+                        Control::Autopilot => Control::Manual,
+                        Control::Manual => Control::Autopilot,
+                    }
+                }
+                Key::Quit => {
+                    self.quit = true;
+                    break;
+                }
+            }
 
-        while self.memory.len() <= addr {
-            self.memory.push(0);
+            self.show_status();
         }
 
-        addr
+        joystick
     }
 
-    fn get(&mut self, addr: Addr, mode: u32) -> Cell {
-        let addr = self.addr(addr, mode);
-        self.memory[addr]
+    pub fn score(&self) -> Cell {
+        self.screen.value(-1, 0).unwrap_or(0)
     }
 
-    fn set(&mut self, addr: Addr, mode: u32, value: Cell) {
-        let addr = self.addr(addr, mode);
-        self.memory[addr] = value;
+    pub fn input(&mut self, data: Cell) {
+        match self.screen.draw(data) {
+            Some(Update::Register { value, .. }) => self.renderer.score(value),
+            Some(Update::Tile {
+                x,
+                y,
+                before,
+                after,
+            }) => {
+                if before == Some(2) && after != 2 {
+                    *self.broken.last_mut().unwrap() += 1;
+                }
+
+                match after {
+                    3 => self.paddle = Point::new(x, y),
+                    4 => self.ball = Point::new(x, y),
+                    _ => (),
+                }
+
+                self.renderer.tile(x, y, after);
+            }
+            None => (),
+        }
     }
+}
 
-    fn op_3<F>(&mut self, mut modes: Modes, op: F)
-    where
-        F: Fn(Cell, Cell) -> Cell,
-    {
-        modes.ensure(3);
+impl Device for Game {
+    fn read(&mut self) -> Cell {
+        let score = self.score();
+        self.renderer.frame(&self.screen, score);
+        if let Some(recorder) = &mut self.recorder {
+            recorder.capture(&self.screen, score, self.ball);
+        }
 
-        let arg1 = self.get(self.pc + 1, modes.0[0]);
-        let arg2 = self.get(self.pc + 2, modes.0[1]);
-        self.set(self.pc + 3, modes.0[2], op(arg1, arg2));
-        self.pc += 4;
-    }
+        let joystick = self.controls();
 
-    fn conditional_jump<F>(&mut self, mut modes: Modes, op: F)
-    where
-        F: Fn(Cell) -> bool,
-    {
-        modes.ensure(2);
-        if op(self.get(self.pc + 1, modes.0[0])) {
-            self.pc = self.get(self.pc + 2, modes.0[1]) as Addr;
-        } else {
-            self.pc += 3;
+        let joystick = match self.control {
+            Control::Autopilot => self.autopilot(),
+            Control::Manual => joystick.unwrap_or(0),
+        };
+
+        if joystick != 0 {
+            self.moves += 1;
         }
-    }
 
-    fn read_stdin(&mut self, mut modes: Modes) {
-        modes.ensure(1);
-        std::thread::sleep(std::time::Duration::from_millis(1));
-        let value = if self.game.ball < self.game.paddle {
-            -1
-        } else if self.game.ball > self.game.paddle {
-            1
-        } else {
-            0
-        };
-        self.set(self.pc + 1, modes.0[0], value);
-        self.pc += 2;
+        self.last_ball = Some(self.ball);
+        self.broken.push(0);
+        joystick
     }
 
-    fn write_stdout(&mut self, mut modes: Modes) {
-        modes.ensure(1);
-        let value = self.get(self.pc + 1, modes.0[0]);
-        self.game.input(value);
-        self.pc += 2;
+    fn write(&mut self, value: Cell) {
+        self.input(value);
     }
+}
 
-    fn set_relative_base(&mut self, mut modes: Modes) {
-        modes.ensure(1);
-        self.relative_base += self.get(self.pc + 1, modes.0[0]);
-        self.pc += 2;
-    }
+fn usage() -> ! {
+    eprintln!("usage: day13-2 PROGRAM [--render curses|text|none] [--play] [--tick MS]");
+    eprintln!("               [--autopilot predict|track] [--stats]");
+    eprintln!("               [--record FILE | --replay FILE] [--patch FILE]...");
+    eprintln!("               [--cast FILE] [--gif FILE] [--apng FILE] [--delay MS] [--scale N]");
+    eprintln!();
+    eprintln!("--play drives the paddle with the arrow keys. In curses, p pauses,");
+    eprintln!("a switches between the player and the autopilot and q quits.");
+    std::process::exit(2);
+}
 
-    pub fn run(&mut self) {
-        loop {
-            let (opcode, modes) = self.get_op();
-            match opcode {
-                1 => self.op_3(modes, |a, b| a + b),
-                2 => self.op_3(modes, |a, b| a * b),
-                3 => self.read_stdin(modes),
-                4 => self.write_stdout(modes),
-                5 => self.conditional_jump(modes, |v| v != 0),
-                6 => self.conditional_jump(modes, |v| v == 0),
-                7 => self.op_3(modes, |a, b| if a < b { 1 } else { 0 }),
-                8 => self.op_3(modes, |a, b| if a == b { 1 } else { 0 }),
-                9 => self.set_relative_base(modes),
-                99 => break,
-                x => panic!("unknown opcode: {}", x),
-            };
+fn main() -> std::io::Result<()> {
+    let prog = std::env::args().nth(1).unwrap_or_else(|| usage());
+
+    let file = File::open(prog)?;
+    let mut buf_reader = BufReader::new(file);
+    let mut data = String::new();
+    buf_reader.read_to_string(&mut data)?;
+
+    let mut memory = intcode::parse(&data);
+
+    memory[0] = 2;
+
+    let mut render = String::from("curses");
+    let mut control = Control::Autopilot;
+    let mut tick = None;
+    let mut policy = Policy::Predict;
+    let mut stats = false;
+    let mut record = None;
+    let mut replay = None;
+    let mut patches = Vec::new();
+    let mut cast = None;
+    let mut gif = None;
+    let mut apng = None;
+    let mut delay = 30;
+    let mut scale = 2;
+
+    let mut args = std::env::args().skip(2);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--render" => render = args.next().unwrap_or_else(|| usage()),
+            "--play" => control = Control::Manual,
+            "--tick" => {
+                let ms = args.next().unwrap_or_else(|| usage());
+                tick = Some(ms.parse().unwrap_or_else(|_| usage()));
+            }
+            "--autopilot" => {
+                policy = match args.next().as_deref() {
+                    Some("predict") => Policy::Predict,
+                    Some("track") => Policy::Track,
+                    _ => usage(),
+                }
+            }
+            "--stats" => stats = true,
+            "--record" | "--replay" if record.is_some() || replay.is_some() => usage(),
+            "--record" => record = Some(args.next().unwrap_or_else(|| usage())),
+            "--replay" => replay = Some(args.next().unwrap_or_else(|| usage())),
+            "--cast" => cast = Some(args.next().unwrap_or_else(|| usage())),
+            "--gif" => gif = Some(args.next().unwrap_or_else(|| usage())),
+            "--apng" => apng = Some(args.next().unwrap_or_else(|| usage())),
+            "--delay" => {
+                let ms = args.next().unwrap_or_else(|| usage());
+                delay = ms.parse().unwrap_or_else(|_| usage());
+            }
+            "--scale" => {
+                scale = match args.next().map(|n| n.parse()) {
+                    Some(Ok(n)) if n >= 1 => n,
+                    _ => {
+                        eprintln!("--scale needs a whole number, at least 1");
+                        usage();
+                    }
+                }
+            }
+            "--patch" => patches.extend(patch::load(args.next().unwrap_or_else(|| usage()))?),
+            _ => usage(),
         }
     }
 
-    #[allow(dead_code)]
-    pub fn dump(&self) {
-        let contents: Vec<String> = self.memory.iter().map(|e| e.to_string()).collect();
-        println!("{}", contents.join(","));
+    let renderer: Box<dyn Renderer> = match render.as_str() {
+        "curses" => Box::new(Curses::new()),
+        _ if control == Control::Manual => usage(),
+        "text" => Box::new(Text::new(io::stdout())),
+        "none" => Box::new(Headless),
+        _ => usage(),
+    };
+
+    // The autopilot only needs slowing down enough to watch.
+    let tick = match (tick, control) {
+        (Some(ms), _) => ms,
+        (None, Control::Autopilot) => 1,
+        (None, Control::Manual) => 100,
+    };
+    let mut game = Game::new(renderer, control, Duration::from_millis(tick));
+    game.policy = policy;
+    if cast.is_some() || gif.is_some() || apng.is_some() {
+        game.recorder = Some(Recorder::new());
     }
-}
-
-#[derive(Debug)]
-struct Modes(pub Vec<u32>);
+    let mut cpu = Intcode::new(&memory, game);
+    cpu.patch(&patches);
 
-impl Modes {
-    pub fn new(values: Vec<u32>) -> Self {
-        Modes(values)
+    if let Some(path) = record {
+        cpu.record(session::create(path)?);
     }
 
-    pub fn ensure(&mut self, size: usize) {
-        while self.0.len() < size {
-            self.0.push(0);
-        }
+    if let Some(path) = replay {
+        cpu.replay(session::load(path)?);
     }
-}
 
-struct Game {
-    screen: HashMap<(Cell, Cell), Cell>,
-    x_buffer: Option<Cell>,
-    y_buffer: Option<Cell>,
-    window: Window,
-    ball: Cell,
-    paddle: Cell,
-    score: Cell,
-}
+    cpu.device.show_status();
+    while !cpu.device.quit && cpu.step() {}
+    cpu.device.renderer.finish();
 
-impl Game {
-    pub fn new() -> Self {
-        let window = initscr();
-        noecho();
-        window.clear();
+    if let Some(mut recorder) = cpu.device.recorder.take() {
+        recorder.push(&cpu.device.screen, cpu.device.score());
 
-        Game {
-            screen: HashMap::new(),
-            x_buffer: None,
-            y_buffer: None,
-            window,
-            ball: 0,
-            paddle: 0,
-            score: 0,
+        let delay = Duration::from_millis(delay);
+        if let Some(path) = cast {
+            record::asciicast(recorder.frames(), delay, File::create(path)?)?;
+        }
+        if let Some(path) = gif {
+            record::gif(recorder.frames(), delay, scale, File::create(path)?)?;
+        }
+        if let Some(path) = apng {
+            record::apng(recorder.frames(), delay, scale, File::create(path)?)?;
         }
     }
 
-    pub fn input(&mut self, data: Cell) {
-        if let None = self.x_buffer {
-            self.x_buffer = Some(data);
-        } else if let None = self.y_buffer {
-            self.y_buffer = Some(data);
-        } else {
-            let x = self.x_buffer.unwrap();
-            let y = self.y_buffer.unwrap();
-
-            if x == -1 && y == 0 {
-                self.window.mvaddstr(0, 60, format!("{}", data));
-                self.score = data;
-            } else {
-                self.screen.insert((x, y), data);
-
-                let ch = match data {
-                    1 => '+',
-                    2 => '#',
-                    3 => {
-                        self.paddle = x;
-                        '-'
-                    }
-                    4 => {
-                        self.ball = x;
-                        '*'
-                    }
-                    _ => ' ',
-                };
+    println!("{}", cpu.device.score());
+    if stats {
+        eprint!("{}", cpu.device.stats());
+    }
 
-                self.window.mvaddch(y, x, ch);
-            }
+    Ok(())
+}
 
-            self.window.refresh();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            self.x_buffer = None;
-            self.y_buffer = None;
-        }
+    fn play_with(renderer: Box<dyn Renderer>, policy: Policy) -> Game {
+        let mut memory = intcode::parse(include_str!("../input.txt"));
+        memory[0] = 2;
+
+        let mut game = Game::new(renderer, Control::Autopilot, Duration::from_millis(1));
+        game.policy = policy;
+        let mut cpu = Intcode::new(&memory, game);
+        cpu.run();
+        cpu.device
     }
-}
 
-fn main() -> std::io::Result<()> {
-    let prog = std::env::args().nth(1).unwrap();
+    fn play(renderer: Box<dyn Renderer>) -> Game {
+        play_with(renderer, Policy::Predict)
+    }
 
-    let file = File::open(prog)?;
-    let mut buf_reader = BufReader::new(file);
-    let mut data = String::new();
-    buf_reader.read_to_string(&mut data)?;
+    #[test]
+    fn headless() {
+        let game = play(Box::new(Headless));
+        assert_eq!(game.score(), 21651);
+        assert_eq!(game.screen.count(2), 0);
+    }
 
-    let mut memory: Vec<Cell> = data
-        .trim()
-        .split(',')
-        .map(|e| Cell::from_str_radix(e, 10).unwrap())
-        .collect();
+    #[test]
+    fn text_frames() {
+        let game = play(Box::new(Text::new(Vec::new())));
+        let text = render::render(&game.screen, game.score());
+        let lines: Vec<&str> = text.lines().collect();
 
-    memory[0] = 2;
+        assert_eq!(lines[0], "score 21651");
+        assert_eq!(lines[1], "+".repeat(41));
+        assert_eq!(lines.len(), 1 + 24);
+    }
 
-    let mut cpu = Intcode::new(&memory);
-    cpu.run();
-    cpu.game.window.getch();
-    endwin();
+    #[test]
+    fn manual_without_keys_holds_still() {
+        let mut game = Game::new(
+            Box::new(Headless),
+            Control::Manual,
+            Duration::from_millis(1),
+        );
+        game.input(20);
+        game.input(22);
+        game.input(3);
+        game.input(18);
+        game.input(19);
+        game.input(4);
+
+        assert_eq!(game.read(), 0);
+        game.control = Control::Autopilot;
+        assert_eq!(game.read(), -1);
+    }
 
-    println!("{}", cpu.game.score);
+    #[test]
+    fn both_autopilots_finish() {
+        let track = play_with(Box::new(Headless), Policy::Track);
+        let predict = play_with(Box::new(Headless), Policy::Predict);
 
-    Ok(())
+        assert_eq!(track.score(), 21651);
+        assert_eq!(predict.score(), 21651);
+        assert_eq!(track.broken.iter().sum::<u32>(), 420);
+        assert_eq!(predict.broken.iter().sum::<u32>(), 420);
+        assert_eq!(predict.inputs(), track.inputs());
+    }
 }
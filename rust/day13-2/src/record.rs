@@ -0,0 +1,503 @@
+//! Recording the game a frame at a time so it can be watched again,
+//! either in a terminal as an asciicast or as an animated GIF or PNG.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use geometry::Point;
+use intcode::screen::Screen;
+use intcode::Cell;
+
+use crate::render::glyph;
+
+/// Colours for each tile, then the score.
+const PALETTE: [[u8; 3]; 8] = [
+    [0, 0, 0],
+    [128, 128, 128],
+    [70, 130, 220],
+    [240, 240, 240],
+    [240, 200, 40],
+    [255, 255, 255],
+    [200, 40, 200],
+    [0, 0, 0],
+];
+const SCORE: u8 = 5;
+/// Any tile the game doesn't define.
+const UNKNOWN: u8 = 6;
+
+/// ANSI foreground colours matching the palette.
+const ANSI: [u8; 7] = [39, 90, 34, 97, 33, 39, 35];
+
+/// Each tile is this many pixels square before scaling.
+const TILE: usize = 4;
+/// The score sits in a band this tall above the screen.
+const BAND: usize = 7;
+
+/// Digits 3 pixels wide and 5 tall, one row per byte.
+const DIGITS: [[u8; 5]; 10] = [
+    [7, 5, 5, 5, 7],
+    [2, 6, 2, 2, 7],
+    [7, 1, 7, 4, 7],
+    [7, 1, 7, 1, 7],
+    [5, 5, 7, 1, 1],
+    [7, 4, 7, 1, 7],
+    [7, 4, 7, 5, 7],
+    [7, 1, 1, 1, 1],
+    [7, 5, 7, 5, 7],
+    [7, 5, 7, 1, 7],
+];
+const MINUS: [u8; 5] = [0, 0, 7, 0, 0];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    /// Row by row, from (0, 0), with any tile the game doesn't define
+    /// as `UNKNOWN`.
+    pub tiles: Vec<u8>,
+    pub score: Cell,
+}
+
+impl Frame {
+    pub fn new(screen: &Screen, score: Cell) -> Self {
+        let (width, height) = match screen.bounds() {
+            Some(bounds) => (
+                (bounds.max.x + 1).max(0) as usize,
+                (bounds.max.y + 1).max(0) as usize,
+            ),
+            None => (0, 0),
+        };
+        let mut tiles = vec![0; width * height];
+
+        for (Point { x, y }, &tile) in screen.tiles().iter() {
+            if x >= 0 && y >= 0 {
+                tiles[y as usize * width + x as usize] = match tile {
+                    0..=4 => tile as u8,
+                    _ => UNKNOWN,
+                };
+            }
+        }
+
+        Frame {
+            width,
+            height,
+            tiles,
+            score,
+        }
+    }
+
+    pub fn tile(&self, x: usize, y: usize) -> u8 {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x]
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    ball: Option<Point>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Keeps a frame if the ball has moved since the last one.
+    pub fn capture(&mut self, screen: &Screen, score: Cell, ball: Point) {
+        if self.ball != Some(ball) {
+            self.ball = Some(ball);
+            self.push(screen, score);
+        }
+    }
+
+    pub fn push(&mut self, screen: &Screen, score: Cell) {
+        self.frames.push(Frame::new(screen, score));
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+fn size(frames: &[Frame]) -> (usize, usize) {
+    let width = frames.iter().map(|f| f.width).max().unwrap_or(0);
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0);
+    (width, height)
+}
+
+/// Writes an asciicast v2 recording, one event per frame. After the
+/// first frame only the rows that changed are redrawn.
+pub fn asciicast<W: Write>(frames: &[Frame], delay: Duration, mut output: W) -> io::Result<()> {
+    let (width, height) = size(frames);
+    writeln!(
+        output,
+        "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+        width,
+        height + 1
+    )?;
+
+    let mut before: Option<&Frame> = None;
+    for (n, frame) in frames.iter().enumerate() {
+        let mut text = String::new();
+        if before.is_none() {
+            text.push_str("\x1b[2J");
+        }
+        if before.map(|b| b.score) != Some(frame.score) {
+            write!(text, "\x1b[1;1H\x1b[0mscore {}\x1b[K", frame.score).unwrap();
+        }
+
+        for y in 0..height {
+            let row = (0..width).map(|x| frame.tile(x, y));
+            if let Some(before) = before {
+                if row.clone().eq((0..width).map(|x| before.tile(x, y))) {
+                    continue;
+                }
+            }
+
+            write!(text, "\x1b[{};1H", y + 2).unwrap();
+            let mut colour = None;
+            for tile in row {
+                if colour != Some(tile) {
+                    let ansi = ANSI.get(tile as usize).unwrap_or(&ANSI[UNKNOWN as usize]);
+                    write!(text, "\x1b[{}m", ansi).unwrap();
+                    colour = Some(tile);
+                }
+                text.push(glyph(tile as Cell));
+            }
+            text.push_str("\x1b[0m");
+        }
+
+        let time = (delay * n as u32).as_secs_f64();
+        writeln!(output, "[{:.3}, \"o\", \"{}\"]", time, escape(&text))?;
+        before = Some(frame);
+    }
+
+    output.flush()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Part of an image, as (x, y, width, height).
+type Rect = (usize, usize, usize, usize);
+
+/// A frame drawn as palette indices.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(frame: &Frame, width: usize, height: usize, scale: usize) -> Self {
+        let mut image = Image {
+            width: width * TILE * scale,
+            height: (BAND + height * TILE) * scale,
+            pixels: Vec::new(),
+        };
+        image.pixels = vec![0; image.width * image.height];
+
+        // The score is cut off at the right edge if the screen's too
+        // narrow for it.
+        let right = width * TILE;
+        let score = frame.score.to_string();
+        for (n, c) in score.bytes().enumerate() {
+            let rows = match c {
+                b'-' => &MINUS,
+                _ => &DIGITS[(c - b'0') as usize],
+            };
+            for (dy, row) in rows.iter().enumerate() {
+                for dx in 0..3 {
+                    let x = 1 + n * 4 + dx;
+                    if row & (4 >> dx) != 0 && x < right {
+                        image.fill(x, 1 + dy, 1, 1, SCORE, scale);
+                    }
+                }
+            }
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let (left, top) = (x * TILE, BAND + y * TILE);
+                match frame.tile(x, y) {
+                    0 => (),
+                    2 => image.fill(left, top, TILE - 1, TILE - 1, 2, scale),
+                    3 => image.fill(left, top, TILE, TILE / 2, 3, scale),
+                    4 => image.fill(left + 1, top + 1, TILE / 2, TILE / 2, 4, scale),
+                    1 => image.fill(left, top, TILE, TILE, 1, scale),
+                    _ => image.fill(left, top, TILE, TILE, UNKNOWN, scale),
+                }
+            }
+        }
+
+        image
+    }
+
+    fn fill(&mut self, x: usize, y: usize, w: usize, h: usize, colour: u8, scale: usize) {
+        for py in y * scale..(y + h) * scale {
+            let row = py * self.width;
+            for px in x * scale..(x + w) * scale {
+                self.pixels[row + px] = colour;
+            }
+        }
+    }
+
+    /// The smallest rectangle holding every pixel that differs from
+    /// `before`.
+    fn changed(&self, before: &Image) -> Rect {
+        let (mut left, mut top) = (self.width, self.height);
+        let (mut right, mut bottom) = (0, 0);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = y * self.width + x;
+                if self.pixels[i] != before.pixels[i] {
+                    left = left.min(x);
+                    top = top.min(y);
+                    right = right.max(x + 1);
+                    bottom = bottom.max(y + 1);
+                }
+            }
+        }
+
+        if right == 0 {
+            (0, 0, 1, 1)
+        } else {
+            (left, top, right - left, bottom - top)
+        }
+    }
+
+    fn crop(&self, (x, y, w, h): Rect) -> Vec<u8> {
+        (y..y + h)
+            .flat_map(|row| &self.pixels[row * self.width + x..row * self.width + x + w])
+            .cloned()
+            .collect()
+    }
+}
+
+/// The size of the images `frames` draw to.
+fn canvas(frames: &[Frame], scale: usize) -> (usize, usize) {
+    let (width, height) = size(frames);
+    (width * TILE * scale, (BAND + height * TILE) * scale)
+}
+
+/// Draws each frame in turn and hands over the part that changed from the
+/// one before, or all of the first one.
+fn each_change<F>(frames: &[Frame], scale: usize, mut f: F) -> io::Result<()>
+where
+    F: FnMut(Rect, Vec<u8>) -> io::Result<()>,
+{
+    let (width, height) = size(frames);
+    let mut before: Option<Image> = None;
+
+    for frame in frames {
+        let image = Image::new(frame, width, height, scale);
+        let rect = match &before {
+            Some(before) => image.changed(before),
+            None => (0, 0, image.width, image.height),
+        };
+        f(rect, image.crop(rect))?;
+        before = Some(image);
+    }
+
+    Ok(())
+}
+
+fn invalid<E: ToString>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+pub fn gif<W: Write>(frames: &[Frame], delay: Duration, scale: usize, output: W) -> io::Result<()> {
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    let (width, height) = canvas(frames, scale);
+    let palette: Vec<u8> = PALETTE.iter().flatten().cloned().collect();
+    let mut encoder =
+        gif::Encoder::new(output, width as u16, height as u16, &palette).map_err(invalid)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(invalid)?;
+
+    each_change(frames, scale, |(x, y, w, h), pixels| {
+        let mut frame = gif::Frame::from_indexed_pixels(w as u16, h as u16, pixels, None);
+        frame.left = x as u16;
+        frame.top = y as u16;
+        frame.delay = (delay.as_millis() / 10) as u16;
+        encoder.write_frame(&frame).map_err(invalid)
+    })
+}
+
+pub fn apng<W: Write>(
+    frames: &[Frame],
+    delay: Duration,
+    scale: usize,
+    output: W,
+) -> io::Result<()> {
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    let (width, height) = canvas(frames, scale);
+    let mut encoder = png::Encoder::new(output, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(PALETTE.iter().flatten().cloned().collect::<Vec<u8>>());
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(invalid)?;
+    encoder
+        .set_frame_delay(delay.as_millis() as u16, 1000)
+        .map_err(invalid)?;
+
+    let mut writer = encoder.write_header().map_err(invalid)?;
+    each_change(frames, scale, |(x, y, w, h), pixels| {
+        writer.reset_frame_position().map_err(invalid)?;
+        writer
+            .set_frame_dimension(w as u32, h as u32)
+            .map_err(invalid)?;
+        writer
+            .set_frame_position(x as u32, y as u32)
+            .map_err(invalid)?;
+        writer.write_image_data(&pixels).map_err(invalid)
+    })?;
+
+    writer.finish().map_err(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(ball: Point) -> Screen {
+        let mut screen = Screen::new();
+        let mut draw = |x, y, tile| {
+            screen.draw(x);
+            screen.draw(y);
+            screen.draw(tile);
+        };
+
+        for x in 0..5 {
+            draw(x, 0, 1);
+        }
+        draw(1, 1, 2);
+        draw(2, 3, 3);
+        draw(ball.x, ball.y, 4);
+        screen
+    }
+
+    #[test]
+    fn captures_when_the_ball_moves() {
+        let mut recorder = Recorder::new();
+        recorder.capture(&screen(Point::new(1, 2)), 0, Point::new(1, 2));
+        recorder.capture(&screen(Point::new(1, 2)), 0, Point::new(1, 2));
+        recorder.capture(&screen(Point::new(2, 2)), 4, Point::new(2, 2));
+
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[1].width, frames[1].height), (5, 4));
+        assert_eq!(frames[1].tile(2, 2), 4);
+        assert_eq!(frames[1].tile(1, 1), 2);
+        assert_eq!(frames[1].score, 4);
+    }
+
+    #[test]
+    fn asciicast_events() {
+        let frames = vec![
+            Frame::new(&screen(Point::new(1, 2)), 0),
+            Frame::new(&screen(Point::new(2, 2)), 7),
+        ];
+        let mut cast = Vec::new();
+        asciicast(&frames, Duration::from_millis(50), &mut cast).unwrap();
+
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "{\"version\": 2, \"width\": 5, \"height\": 5}");
+        assert!(lines[1].starts_with("[0.000, \"o\", \"\\u001b[2J\\u001b[1;1H\\u001b[0mscore 0"));
+        assert_eq!(
+            lines[2],
+            "[0.050, \"o\", \"\\u001b[1;1H\\u001b[0mscore 7\\u001b[K\\u001b[4;1H\\u001b[39m  \\u001b[33m*\\u001b[39m  \\u001b[0m\"]"
+        );
+    }
+
+    #[test]
+    fn odd_tiles_and_negative_scores() {
+        let mut screen = screen(Point::new(1, 2));
+        for value in &[0, 4, 9] {
+            screen.draw(*value);
+        }
+        let frame = Frame::new(&screen, -12);
+        assert_eq!(frame.tile(0, 4), UNKNOWN);
+
+        let mut cast = Vec::new();
+        asciicast(
+            std::slice::from_ref(&frame),
+            Duration::from_millis(50),
+            &mut cast,
+        )
+        .unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+        assert!(cast.contains("score -12"));
+        assert!(cast.contains("\\u001b[35m?"));
+
+        let image = Image::new(&frame, frame.width, frame.height, 1);
+        assert!(image.pixels.iter().all(|&p| (p as usize) < PALETTE.len()));
+        // The minus sign's bar, left of the digits.
+        assert_eq!(
+            &image.pixels[3 * image.width + 1..3 * image.width + 4],
+            &[SCORE; 3]
+        );
+        assert_eq!(image.pixels[(BAND + 16) * image.width + 1], UNKNOWN);
+
+        let mut gif = Vec::new();
+        super::gif(&[frame], Duration::from_millis(50), 1, &mut gif).unwrap();
+    }
+
+    #[test]
+    fn scores_wider_than_the_screen() {
+        let mut screen = Screen::new();
+        for value in &[1, 0, 2] {
+            screen.draw(*value);
+        }
+        let frame = Frame::new(&screen, 12345);
+        let image = Image::new(&frame, frame.width, frame.height, 2);
+        assert_eq!(image.width, 2 * TILE * 2);
+        assert!(image.pixels.contains(&SCORE));
+    }
+
+    #[test]
+    fn images_only_redraw_changes() {
+        let frames = vec![
+            Frame::new(&screen(Point::new(1, 2)), 0),
+            Frame::new(&screen(Point::new(2, 2)), 0),
+        ];
+        let mut rects = Vec::new();
+        each_change(&frames, 1, |rect, pixels| {
+            assert_eq!(pixels.len(), rect.2 * rect.3);
+            rects.push(rect);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(rects, vec![(0, 0, 20, BAND + 16), (5, BAND + 9, 6, 2)]);
+    }
+}
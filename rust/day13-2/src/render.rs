@@ -0,0 +1,132 @@
+use std::io::prelude::*;
+use std::time::Duration;
+
+use intcode::screen::Screen;
+use intcode::Cell;
+use pancurses::{curs_set, endwin, initscr, noecho, Input, Window};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Left,
+    Right,
+    Pause,
+    Autopilot,
+    Quit,
+}
+
+/// Something that shows the game as it's played. Every method does nothing
+/// by default, which is all a headless run needs.
+pub trait Renderer {
+    /// A tile was drawn.
+    fn tile(&mut self, _x: Cell, _y: Cell, _tile: Cell) {}
+
+    /// The score display changed.
+    fn score(&mut self, _score: Cell) {}
+
+    /// The game is waiting on the joystick, so the screen is complete.
+    fn frame(&mut self, _screen: &Screen, _score: Cell) {}
+
+    /// Shows what the controls are doing.
+    fn status(&mut self, _status: &str) {}
+
+    /// Waits up to `wait` for a key, for renderers that have a keyboard.
+    fn key(&mut self, _wait: Duration) -> Option<Key> {
+        None
+    }
+
+    /// The game is over.
+    fn finish(&mut self) {}
+}
+
+/// How each tile is drawn as text.
+pub const TILES: [(Cell, char); 5] = [(0, ' '), (1, '+'), (2, '#'), (3, '-'), (4, '*')];
+
+/// Any tile the game doesn't define comes out as `?`, so it still shows.
+pub fn glyph(tile: Cell) -> char {
+    TILES
+        .iter()
+        .find(|&&(t, _)| t == tile)
+        .map_or('?', |&(_, c)| c)
+}
+
+pub struct Headless;
+
+impl Renderer for Headless {}
+
+pub struct Curses {
+    window: Window,
+}
+
+impl Curses {
+    pub fn new() -> Self {
+        let window = initscr();
+        noecho();
+        curs_set(0);
+        window.keypad(true);
+        window.clear();
+
+        Curses { window }
+    }
+}
+
+impl Renderer for Curses {
+    fn tile(&mut self, x: Cell, y: Cell, tile: Cell) {
+        self.window.mvaddch(y as i32, x as i32, glyph(tile));
+        self.window.refresh();
+    }
+
+    fn score(&mut self, score: Cell) {
+        self.window.mvaddstr(0, 60, format!("{}", score));
+        self.window.refresh();
+    }
+
+    fn status(&mut self, status: &str) {
+        self.window.mv(1, 60);
+        self.window.clrtoeol();
+        self.window.addstr(status);
+        self.window.refresh();
+    }
+
+    fn key(&mut self, wait: Duration) -> Option<Key> {
+        self.window.timeout(wait.as_millis().max(1) as i32);
+
+        match self.window.getch()? {
+            Input::KeyLeft | Input::Character('h') => Some(Key::Left),
+            Input::KeyRight | Input::Character('l') => Some(Key::Right),
+            Input::Character('p') | Input::Character(' ') => Some(Key::Pause),
+            Input::Character('a') => Some(Key::Autopilot),
+            Input::Character('q') => Some(Key::Quit),
+            _ => self.key(Duration::from_millis(1)),
+        }
+    }
+
+    fn finish(&mut self) {
+        endwin();
+    }
+}
+
+/// Writes out every frame as plain text, with the score above it.
+pub struct Text<W: Write> {
+    output: W,
+}
+
+impl<W: Write> Text<W> {
+    pub fn new(output: W) -> Self {
+        Text { output }
+    }
+}
+
+impl<W: Write> Renderer for Text<W> {
+    fn frame(&mut self, screen: &Screen, score: Cell) {
+        write!(self.output, "{}", render(screen, score)).unwrap();
+    }
+
+    fn finish(&mut self) {
+        self.output.flush().unwrap();
+    }
+}
+
+/// Draws the screen as text, with the score above it.
+pub fn render(screen: &Screen, score: Cell) -> String {
+    format!("score {}\n{}", score, screen.render())
+}
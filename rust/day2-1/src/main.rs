@@ -1,16 +1,6 @@
 use std::io::{self, Read};
 
-fn main() {
-    let mut data = String::new();
-
-    io::stdin().read_to_string(&mut data).unwrap();
-
-    let mut memory: Vec<u32> = data
-        .trim()
-        .split(',')
-        .map(|e| u32::from_str_radix(e, 10).unwrap())
-        .collect();
-
+fn run(memory: &mut [u32]) {
     let mut pc: usize = 0;
 
     loop {
@@ -33,7 +23,36 @@ fn main() {
             _ => panic!("wtf"),
         };
     }
+}
+
+fn main() {
+    let mut data = String::new();
+
+    io::stdin().read_to_string(&mut data).unwrap();
+
+    let mut memory: Vec<u32> = data
+        .trim()
+        .split(',')
+        .map(|e| e.parse::<u32>().unwrap())
+        .collect();
+
+    run(&mut memory);
 
     let memory: Vec<String> = memory.iter().map(|e| e.to_string()).collect();
     println!("{}", memory.join(","));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::conformance;
+
+    #[test]
+    fn conformance() {
+        conformance::check(&[], |case| {
+            let mut memory: Vec<u32> = case.program.iter().map(|&e| e as u32).collect();
+            run(&mut memory);
+            (Vec::new(), memory.iter().map(|&e| e.into()).collect())
+        });
+    }
+}
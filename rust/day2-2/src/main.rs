@@ -45,7 +45,7 @@ fn main() {
     let memory: Vec<u32> = data
         .trim()
         .split(',')
-        .map(|e| u32::from_str_radix(e, 10).unwrap())
+        .map(|e| e.parse::<u32>().unwrap())
         .collect();
 
     let mut found = false;
@@ -71,3 +71,18 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::conformance;
+
+    #[test]
+    fn conformance() {
+        conformance::check(&[], |case| {
+            let mut cpu = Intcode::new(case.program.iter().map(|&e| e as u32).collect());
+            cpu.run();
+            (Vec::new(), cpu.memory.iter().map(|&e| e.into()).collect())
+        });
+    }
+}
@@ -1,91 +1,43 @@
-use std::collections::HashSet;
 use std::io;
 use std::io::prelude::*;
+use std::process;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
-
-    pub fn manhattan(&self) -> u32 {
-        (self.x.abs() + self.y.abs()) as u32
-    }
-}
+use geometry::moves::Parser;
+use geometry::polyline::{self, Crossing};
+use geometry::{Point, Polyline};
 
-struct Wire {
-    x: i32,
-    y: i32,
-    pub points: HashSet<Point>,
-}
+fn main() -> io::Result<()> {
+    let mut diagonals = false;
 
-impl Wire {
-    pub fn new() -> Self {
-        Wire {
-            x: 0,
-            y: 0,
-            points: HashSet::new(),
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--diagonals" => diagonals = true,
+            x => panic!("unknown option: {}", x),
         }
     }
 
-    pub fn add_span(&mut self, direction: char, distance: i32) {
-        match direction {
-            'R' => {
-                for wx in 0..distance {
-                    self.points.insert(Point::new(self.x + wx, self.y));
-                }
-                self.x += distance;
-            }
-            'L' => {
-                for wx in 0..distance {
-                    self.points.insert(Point::new(self.x - wx, self.y));
-                }
-                self.x -= distance;
-            }
-            'U' => {
-                for wy in 0..distance {
-                    self.points.insert(Point::new(self.x, self.y + wy));
-                }
-                self.y += distance;
-            }
-            'D' => {
-                for wy in 0..distance {
-                    self.points.insert(Point::new(self.x, self.y - wy));
-                }
-                self.y -= distance;
-            }
-            _ => (),
-        };
-    }
-}
-
-fn main() {
-    let stdin = io::stdin();
-    let mut wires: Vec<Wire> = Vec::new();
-
-    for line in stdin.lock().lines() {
-        let mut wire = Wire::new();
-
-        let line = line.unwrap();
-        for bit in line.split(',') {
-            let direction = bit.chars().nth(0).unwrap();
-            let distance = i32::from_str_radix(&bit[1..], 10).unwrap();
-
-            wire.add_span(direction, distance);
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    let wires: Vec<Polyline> = match Parser::new().diagonals(diagonals).parse(&input) {
+        Ok(wires) => wires
+            .iter()
+            .map(|moves| Polyline::from_moves(Point::ORIGIN, moves))
+            .collect(),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
-        wires.push(wire);
-    }
+    };
 
-    let crossings = wires[0].points.intersection(&wires[1].points);
-    let mut distances: Vec<u32> = crossings
-        .map(|p| p.manhattan())
-        .filter(|d| *d != 0)
+    // Every wire starts at the central port, which doesn't count.
+    let crossings: Vec<Crossing> = polyline::crossings(&wires)
+        .into_iter()
+        .filter(|c| c.point != Point::ORIGIN)
         .collect();
-    distances.sort();
-    println!("{:?}", distances[0]);
+
+    let nearest = crossings.iter().map(|c| c.distance).min().unwrap();
+    println!("{:?}", nearest);
+
+    Ok(())
 }
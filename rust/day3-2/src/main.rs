@@ -1,153 +1,63 @@
-use std::collections::HashSet;
+use std::fs::{self, File};
 use std::io;
 use std::io::prelude::*;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
-}
-
-struct Wire {
-    spans: Vec<(char, i32)>,
-    x: i32,
-    y: i32,
-    pub points: HashSet<Point>,
-}
-
-impl Wire {
-    pub fn new(spans: Vec<(char, i32)>) -> Self {
-        let mut wire = Wire {
-            spans: spans.clone(),
-            x: 0,
-            y: 0,
-            points: HashSet::new(),
-        };
-
-        for span in spans {
-            wire.add_span(span.0, span.1);
-        }
-
-        wire
-    }
-
-    pub fn add_span(&mut self, direction: char, distance: i32) {
-        match direction {
-            'R' => {
-                for wx in 0..distance {
-                    self.points.insert(Point::new(self.x + wx, self.y));
-                }
-                self.x += distance;
-            }
-            'L' => {
-                for wx in 0..distance {
-                    self.points.insert(Point::new(self.x - wx, self.y));
-                }
-                self.x -= distance;
-            }
-            'U' => {
-                for wy in 0..distance {
-                    self.points.insert(Point::new(self.x, self.y + wy));
-                }
-                self.y += distance;
-            }
-            'D' => {
-                for wy in 0..distance {
-                    self.points.insert(Point::new(self.x, self.y - wy));
-                }
-                self.y -= distance;
-            }
-            _ => (),
-        };
-    }
-
-    pub fn steps_to_intersection(&self, point: Point) -> i32 {
-        let mut steps = 0;
-        let mut x = 0;
-        let mut y = 0;
-
-        for span in self.spans.clone() {
-            match span.0 {
-                'R' => {
-                    if point.y == y && x < point.x && point.x < (x + span.1) {
-                        return steps + (point.x - x);
-                    } else {
-                        steps += span.1;
-                        x += span.1;
-                    }
-                }
-                'L' => {
-                    if point.y == y && x > point.x && point.x > (x - span.1) {
-                        return steps + (x - point.x);
-                    } else {
-                        steps += span.1;
-                        x -= span.1;
-                    }
-                }
-                'U' => {
-                    if point.x == x && y < point.y && point.y < (y + span.1) {
-                        return steps + (point.y - y);
-                    } else {
-                        steps += span.1;
-                        y += span.1;
-                    }
-                }
-                'D' => {
-                    if point.x == x && y > point.y && point.y > (y - span.1) {
-                        return steps + (y - point.y);
-                    } else {
-                        steps += span.1;
-                        y -= span.1;
-                    }
-                }
-                _ => (),
-            }
+use std::process;
+
+use geometry::moves::Parser;
+use geometry::polyline::{self, Crossing};
+use geometry::{Point, Polyline};
+
+mod render;
+
+use crate::render::Picture;
+
+fn main() -> io::Result<()> {
+    let mut svg = None;
+    let mut png = None;
+    let mut size = 800;
+    let mut diagonals = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--svg" => svg = Some(args.next().unwrap()),
+            "--png" => png = Some(args.next().unwrap()),
+            "--size" => size = args.next().unwrap().parse().unwrap(),
+            "--diagonals" => diagonals = true,
+            x => panic!("unknown option: {}", x),
         }
-
-        steps
     }
-}
 
-fn main() {
-    let stdin = io::stdin();
-    let mut wires: Vec<Wire> = Vec::new();
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
 
-    for line in stdin.lock().lines() {
-        let mut spans = Vec::new();
-
-        let line = line.unwrap();
-        for bit in line.split(',') {
-            let direction = bit.chars().nth(0).unwrap();
-            let distance = i32::from_str_radix(&bit[1..], 10).unwrap();
-            spans.push((direction, distance));
+    let wires: Vec<Polyline> = match Parser::new().diagonals(diagonals).parse(&input) {
+        Ok(wires) => wires
+            .iter()
+            .map(|moves| Polyline::from_moves(Point::ORIGIN, moves))
+            .collect(),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
+    };
 
-        wires.push(Wire::new(spans));
-    }
-
-    let crossings: Vec<Point> = wires[0]
-        .points
-        .intersection(&wires[1].points)
-        .map(|p| *p)
+    // Every wire starts at the central port, which doesn't count.
+    let crossings: Vec<Crossing> = polyline::crossings(&wires)
+        .into_iter()
+        .filter(|c| c.point != Point::ORIGIN)
         .collect();
 
-    let mut distances: Vec<i32> = crossings.iter().map(|_| 0).collect();
+    let fewest = crossings.iter().map(|c| c.steps).min().unwrap();
+    println!("{:?}", fewest);
 
-    for wire in wires {
-        distances = crossings
-            .iter()
-            .map(|c| wire.steps_to_intersection(*c))
-            .zip(distances.iter())
-            .map(|(s, d)| s + d)
-            .collect();
+    let picture = Picture::new(&wires, &crossings, size);
+    if let Some(path) = svg {
+        fs::write(path, picture.svg())?;
+    }
+    if let Some(path) = png {
+        picture.png(File::create(path)?)?;
     }
 
-    distances.sort();
-    println!("{:?}", distances[0]);
+    Ok(())
 }
@@ -0,0 +1,321 @@
+//! Pictures of the wires and where they cross, for seeing what the
+//! examples are doing. Inputs of any size are scaled to fit.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use geometry::polyline::Crossing;
+use geometry::{Bounds, Point, Polyline};
+
+/// One colour per wire, going round again if there are more wires.
+const WIRES: [[u8; 3]; 6] = [
+    [230, 25, 75],
+    [60, 180, 75],
+    [67, 99, 216],
+    [245, 130, 49],
+    [145, 30, 180],
+    [66, 212, 244],
+];
+const BACKGROUND: [u8; 3] = [255, 255, 255];
+const MARKER: [u8; 3] = [0, 0, 0];
+const CLOSEST: [u8; 3] = [255, 200, 0];
+const FEWEST: [u8; 3] = [0, 200, 200];
+
+/// Space around the wires, in pixels.
+const MARGIN: f64 = 10.0;
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+pub struct Picture<'a> {
+    wires: &'a [Polyline],
+    crossings: &'a [Crossing],
+    closest: Option<&'a Crossing>,
+    fewest: Option<&'a Crossing>,
+    bounds: Bounds,
+    /// Pixels per step.
+    scale: f64,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Picture<'a> {
+    /// Scales the wires so the longer side of the picture is about
+    /// `size` pixels.
+    pub fn new(wires: &'a [Polyline], crossings: &'a [Crossing], size: usize) -> Self {
+        let points = wires
+            .iter()
+            .flat_map(|w| w.segments().iter().flat_map(|s| vec![s.start, s.end]))
+            .chain(Some(Point::ORIGIN));
+        let bounds = Bounds::around(points).unwrap();
+
+        let extent = (bounds.width().max(bounds.height()) - 1).max(1) as f64;
+        let scale = (size as f64 - 2.0 * MARGIN).max(1.0) / extent;
+        let pixels = |n: usize| ((n - 1) as f64 * scale + 2.0 * MARGIN).ceil() as usize + 1;
+
+        Picture {
+            wires,
+            crossings,
+            closest: crossings.iter().min_by_key(|c| c.distance),
+            fewest: crossings.iter().min_by_key(|c| c.steps),
+            bounds,
+            scale,
+            width: pixels(bounds.width()),
+            height: pixels(bounds.height()),
+        }
+    }
+
+    fn pixel(&self, point: Point) -> (f64, f64) {
+        let offset = point - self.bounds.min;
+        (
+            MARGIN + offset.x as f64 * self.scale,
+            MARGIN + offset.y as f64 * self.scale,
+        )
+    }
+
+    pub fn svg(&self) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+            self.width, self.height
+        );
+        writeln!(
+            svg,
+            "  <rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            self.width,
+            self.height,
+            hex(BACKGROUND)
+        )
+        .unwrap();
+
+        for (n, wire) in self.wires.iter().enumerate() {
+            let points: Vec<String> = Some(wire.start())
+                .into_iter()
+                .chain(wire.segments().iter().map(|s| s.end))
+                .map(|p| {
+                    let (x, y) = self.pixel(p);
+                    format!("{:.1},{:.1}", x, y)
+                })
+                .collect();
+            writeln!(
+                svg,
+                "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+                points.join(" "),
+                hex(WIRES[n % WIRES.len()])
+            )
+            .unwrap();
+        }
+
+        let (x, y) = self.pixel(Point::ORIGIN);
+        writeln!(
+            svg,
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"8\" height=\"8\" fill=\"{}\"><title>origin</title></rect>",
+            x - 4.0,
+            y - 4.0,
+            hex(MARKER)
+        )
+        .unwrap();
+
+        for (crossing, colour, what) in &[
+            (self.closest, CLOSEST, "closest"),
+            (self.fewest, FEWEST, "fewest steps"),
+        ] {
+            if let Some(crossing) = crossing {
+                let (x, y) = self.pixel(crossing.point);
+                writeln!(
+                    svg,
+                    "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"7\" fill=\"none\" stroke=\"{}\" stroke-width=\"3\"><title>{}</title></circle>",
+                    x,
+                    y,
+                    hex(*colour),
+                    what
+                )
+                .unwrap();
+            }
+        }
+
+        for crossing in self.crossings {
+            let (x, y) = self.pixel(crossing.point);
+            writeln!(
+                svg,
+                "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"2.5\" fill=\"{}\"><title>{} distance {} steps {}</title></circle>",
+                x,
+                y,
+                hex(MARKER),
+                crossing.point,
+                crossing.distance,
+                crossing.steps
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Three bytes per pixel, row by row.
+    fn pixels(&self) -> Vec<u8> {
+        let mut canvas = Canvas {
+            width: self.width,
+            height: self.height,
+            pixels: BACKGROUND.repeat(self.width * self.height),
+        };
+
+        for (n, wire) in self.wires.iter().enumerate() {
+            for segment in wire.segments() {
+                canvas.line(
+                    self.pixel(segment.start),
+                    self.pixel(segment.end),
+                    WIRES[n % WIRES.len()],
+                );
+            }
+        }
+
+        canvas.square(self.pixel(Point::ORIGIN), 4, MARKER);
+        for (crossing, colour) in &[(self.closest, CLOSEST), (self.fewest, FEWEST)] {
+            if let Some(crossing) = crossing {
+                canvas.ring(self.pixel(crossing.point), 7.0, 2.0, *colour);
+            }
+        }
+        for crossing in self.crossings {
+            canvas.square(self.pixel(crossing.point), 1, MARKER);
+        }
+
+        canvas.pixels
+    }
+
+    pub fn png<W: Write>(&self, output: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(output, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels())
+            .map_err(io::Error::other)
+    }
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn plot(&mut self, x: i64, y: i64, colour: [u8; 3]) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            let i = (y as usize * self.width + x as usize) * 3;
+            self.pixels[i..i + 3].copy_from_slice(&colour);
+        }
+    }
+
+    /// Wires only run straight or at 45 degrees, so stepping a pixel at
+    /// a time along the longer side is enough.
+    fn line(&mut self, (x0, y0): (f64, f64), (x1, y1): (f64, f64), colour: [u8; 3]) {
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).round().max(1.0);
+        for n in 0..=steps as i64 {
+            let t = n as f64 / steps;
+            let (x, y) = (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+            self.plot(x.round() as i64, y.round() as i64, colour);
+        }
+    }
+
+    fn square(&mut self, (x, y): (f64, f64), radius: i64, colour: [u8; 3]) {
+        let (x, y) = (x.round() as i64, y.round() as i64);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                self.plot(x + dx, y + dy, colour);
+            }
+        }
+    }
+
+    fn ring(&mut self, (x, y): (f64, f64), radius: f64, thickness: f64, colour: [u8; 3]) {
+        let reach = (radius + thickness).ceil() as i64;
+        let (cx, cy) = (x.round() as i64, y.round() as i64);
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let distance = ((dx * dx + dy * dy) as f64).sqrt();
+                if (distance - radius).abs() <= thickness / 2.0 {
+                    self.plot(cx + dx, cy + dy, colour);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use geometry::{polyline, Heading};
+
+    fn crossings(wires: &[Polyline]) -> Vec<Crossing> {
+        polyline::crossings(wires)
+            .into_iter()
+            .filter(|c| c.point != Point::ORIGIN)
+            .collect()
+    }
+
+    fn wires() -> Vec<Polyline> {
+        let runs = |runs: &[(char, i64)]| {
+            let runs: Vec<(Heading, i64)> = runs
+                .iter()
+                .map(|&(c, n)| (Heading::from_letter(c).unwrap(), n))
+                .collect();
+            Polyline::from_runs(Point::ORIGIN, runs)
+        };
+        vec![
+            runs(&[('R', 8), ('U', 5), ('L', 5), ('D', 3)]),
+            runs(&[('U', 7), ('R', 6), ('D', 4), ('L', 4)]),
+        ]
+    }
+
+    #[test]
+    fn scales_to_fit() {
+        let wires = wires();
+        let crossings = crossings(&wires);
+        let picture = Picture::new(&wires, &crossings, 100);
+
+        // Eight steps across and seven up, the longer side filling the
+        // picture.
+        assert_eq!(picture.scale, 10.0);
+        assert_eq!((picture.width, picture.height), (101, 91));
+        assert_eq!(picture.pixel(Point::ORIGIN), (10.0, 80.0));
+    }
+
+    #[test]
+    fn svg_marks_everything() {
+        let wires = wires();
+        let crossings = crossings(&wires);
+        let svg = Picture::new(&wires, &crossings, 100).svg();
+
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("stroke=\"#e6194b\""));
+        assert!(svg.contains("stroke=\"#3cb44b\""));
+        assert!(svg.contains("<title>origin</title>"));
+        assert!(svg.contains("<title>closest</title>"));
+        assert!(svg.contains("<title>fewest steps</title>"));
+        assert!(svg.contains("<title>3,-3 distance 6 steps 40</title>"));
+        assert!(svg.contains("<title>6,-5 distance 11 steps 30</title>"));
+    }
+
+    #[test]
+    fn png_pixels() {
+        let wires = wires();
+        let crossings = crossings(&wires);
+        let picture = Picture::new(&wires, &crossings, 100);
+        let pixels = picture.pixels();
+        let at = |x: usize, y: usize| {
+            let i = (y * picture.width + x) * 3;
+            [pixels[i], pixels[i + 1], pixels[i + 2]]
+        };
+
+        assert_eq!(pixels.len(), 101 * 91 * 3);
+        assert_eq!(at(0, 0), BACKGROUND);
+        // Along the first wire's first run, and up the second's.
+        assert_eq!(at(50, 80), WIRES[0]);
+        assert_eq!(at(10, 40), WIRES[1]);
+        assert_eq!(at(10, 80), MARKER);
+    }
+}
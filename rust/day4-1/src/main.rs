@@ -1,38 +1,52 @@
-const START: u32 = 231832;
-const END: u32 = 767346;
+use std::process;
 
-fn decompose(value: u32) -> Vec<u8> {
-    let mut value = value;
-    let mut digits = Vec::new();
+use passwords::{Rule, Search};
 
-    for _ in 0..6 {
-        digits.insert(0, (value % 10) as u8);
-        value = value / 10;
+fn main() {
+    let mut range = String::from("231832-767346");
+    let mut length = None;
+    let mut rules = Vec::new();
+    let mut list = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--range" => range = args.next().unwrap(),
+            "--length" => length = Some(args.next().unwrap().parse().unwrap()),
+            "--rule" => rules.push(args.next().unwrap()),
+            "--list" => list = true,
+            x => panic!("unknown option: {}", x),
+        }
     }
 
-    digits
-}
-
-fn valid(value: u32) -> bool {
-    let digits = decompose(value);
-
-    let mut dedup = digits.clone();
-    dedup.dedup();
+    let rules = if rules.is_empty() {
+        vec![Rule::NonDecreasing, Rule::Pair]
+    } else {
+        match rules.iter().map(|r| Rule::parse(r)).collect() {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    };
 
-    if dedup.len() == digits.len() {
-        return false;
+    let mut search = match Search::parse(&range) {
+        Ok(search) => search.rules(rules),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    if let Some(length) = length {
+        search = search.length(length);
     }
 
-    for x in 1..digits.len() {
-        if digits[x - 1] > digits[x] {
-            return false;
+    if list {
+        for value in search.matches() {
+            println!("{}", value);
         }
+    } else {
+        println!("{}", search.count());
     }
-
-    true
-}
-
-fn main() {
-    let valid: Vec<u32> = (START..END).filter(|v| valid(*v)).collect();
-    println!("{}", valid.len());
 }
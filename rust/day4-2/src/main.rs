@@ -1,44 +1,52 @@
-const START: u32 = 231832;
-const END: u32 = 767346;
+use std::process;
 
-fn decompose(value: u32) -> Vec<u8> {
-    let mut value = value;
-    let mut digits = Vec::new();
+use passwords::{Rule, Search};
 
-    for _ in 0..6 {
-        digits.insert(0, (value % 10) as u8);
-        value = value / 10;
+fn main() {
+    let mut range = String::from("231832-767346");
+    let mut length = None;
+    let mut rules = Vec::new();
+    let mut list = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--range" => range = args.next().unwrap(),
+            "--length" => length = Some(args.next().unwrap().parse().unwrap()),
+            "--rule" => rules.push(args.next().unwrap()),
+            "--list" => list = true,
+            x => panic!("unknown option: {}", x),
+        }
     }
 
-    digits
-}
-
-fn valid(value: u32) -> bool {
-    let digits = decompose(value);
-    let mut prev_digit: u8 = 0;
-    let mut run_length: u8 = 1;
-    let mut runs: [u8; 10] = [0; 10];
-
-    for x in 0..digits.len() {
-        if x < 5 && digits[x] > digits[x + 1] {
-            return false;
+    let rules = if rules.is_empty() {
+        vec![Rule::NonDecreasing, Rule::ExactPair]
+    } else {
+        match rules.iter().map(|r| Rule::parse(r)).collect() {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
         }
+    };
 
-        if digits[x] == prev_digit {
-            run_length += 1;
-        } else if prev_digit != 0 {
-            runs[prev_digit as usize] = run_length;
-            run_length = 1;
+    let mut search = match Search::parse(&range) {
+        Ok(search) => search.rules(rules),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
-
-        prev_digit = digits[x];
+    };
+    if let Some(length) = length {
+        search = search.length(length);
     }
-    runs[prev_digit as usize] = run_length;
 
-    return runs.contains(&2);
-}
-
-fn main() {
-    let valid: Vec<u32> = (START..END).filter(|v| valid(*v)).collect();
-    println!("{}", valid.len());
+    if list {
+        for value in search.matches() {
+            println!("{}", value);
+        }
+    } else {
+        println!("{}", search.count());
+    }
 }
@@ -61,23 +61,11 @@ impl Memory {
     }
 }
 
-fn main() -> std::io::Result<()> {
-    let prog = std::env::args().nth(1).unwrap();
-
-    let file = File::open(prog)?;
-    let mut buf_reader = BufReader::new(file);
-    let mut data = String::new();
-    buf_reader.read_to_string(&mut data)?;
-
-    let memory: Vec<i32> = data
-        .trim()
-        .split(',')
-        .map(|e| i32::from_str_radix(e, 10).unwrap())
-        .collect();
-    let mut memory = Memory::new(memory);
-
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
+fn run<I, O>(memory: &mut Memory, mut input: I, mut output: O)
+where
+    I: FnMut() -> i32,
+    O: FnMut(i32),
+{
     let mut pc: u32 = 0;
 
     loop {
@@ -93,24 +81,72 @@ fn main() -> std::io::Result<()> {
                 pc += 4;
             }
             3 => {
-                print!("> ");
-                stdout.flush()?;
-                let mut value = String::new();
-                stdin.read_line(&mut value)?;
-                let value = i32::from_str_radix(value.trim(), 10).unwrap();
+                let value = input();
                 memory.set(pc + 1, *modes.first().unwrap_or(&0), value);
                 pc += 2;
             }
             4 => {
-                println!("# {}", memory.get(pc + 1, *modes.first().unwrap_or(&0)));
+                output(memory.get(pc + 1, *modes.first().unwrap_or(&0)));
                 pc += 2;
             }
             99 => break,
             _ => panic!("wtf"),
         };
     }
+}
+
+fn main() -> std::io::Result<()> {
+    let prog = std::env::args().nth(1).unwrap();
+
+    let file = File::open(prog)?;
+    let mut buf_reader = BufReader::new(file);
+    let mut data = String::new();
+    buf_reader.read_to_string(&mut data)?;
+
+    let memory: Vec<i32> = data
+        .trim()
+        .split(',')
+        .map(|e| e.parse::<i32>().unwrap())
+        .collect();
+    let mut memory = Memory::new(memory);
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    run(
+        &mut memory,
+        || {
+            print!("> ");
+            stdout.flush().unwrap();
+            let mut value = String::new();
+            stdin.read_line(&mut value).unwrap();
+            value.trim().parse::<i32>().unwrap()
+        },
+        |value| println!("# {}", value),
+    );
 
     memory.dump();
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::conformance::{self, Feature};
+
+    #[test]
+    fn conformance() {
+        conformance::check(&[Feature::Modes, Feature::Io], |case| {
+            let mut memory = Memory::new(case.program.iter().map(|&e| e as i32).collect());
+            let mut input = case.input.iter().map(|&e| e as i32);
+            let mut output = Vec::new();
+            run(
+                &mut memory,
+                || input.next().unwrap(),
+                |value| output.push(value.into()),
+            );
+            (output, memory.0.iter().map(|&e| e.into()).collect())
+        });
+    }
+}
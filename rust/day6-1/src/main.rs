@@ -1,39 +1,57 @@
-use std::collections::HashMap;
+use std::fs;
 use std::io;
 use std::io::prelude::*;
-
-fn orbit_count(body: &String, bodies: &HashMap<String, String>) -> u32 {
-    let mut count = 0;
-    let mut body = body;
-
-    loop {
-        count += 1;
-        body = bodies.get(body).unwrap();
-
-        if body.as_str() == "COM" {
-            break;
+use std::process;
+
+use orbits::{render, Tree};
+
+fn main() -> io::Result<()> {
+    let mut dot = None;
+    let mut text = None;
+    let mut highlight = None;
+    let mut fold = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dot" => dot = Some(args.next().unwrap()),
+            "--tree" => text = Some(args.next().unwrap()),
+            "--fold" => fold = true,
+            "--highlight" => highlight = Some((args.next().unwrap(), args.next().unwrap())),
+            x => panic!("unknown option: {}", x),
         }
     }
 
-    count
-}
-
-fn main() {
-    let mut bodies: HashMap<String, String> = HashMap::new();
-    let stdin = io::stdin();
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
 
-    for line in stdin.lock().lines() {
-        let line = line.unwrap();
-
-        let pair: Vec<&str> = line.split(')').collect();
-        bodies.insert(pair[1].to_string(), pair[0].to_string());
+    let tree = match Tree::parse(&input) {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let highlight = highlight.map(|(a, b)| {
+        let body = |name: &str| match tree.body(name) {
+            Some(body) => body,
+            None => {
+                eprintln!("no body called {}", name);
+                process::exit(1);
+            }
+        };
+        (body(&a), body(&b))
+    });
+
+    println!("{}", tree.total_orbits());
+
+    if let Some(path) = dot {
+        fs::write(path, render::dot(&tree, highlight))?;
     }
-
-    let mut total = 0;
-
-    for body in bodies.keys() {
-        total += orbit_count(&body, &bodies);
+    if let Some(path) = text {
+        fs::write(path, render::text(&tree, fold))?;
     }
 
-    println!("{}", total);
+    Ok(())
 }
@@ -12,9 +12,9 @@ struct Intcode {
 }
 
 impl Intcode {
-    pub fn new(contents: &Vec<i32>, input: Vec<i32>) -> Self {
+    pub fn new(contents: &[i32], input: Vec<i32>) -> Self {
         Intcode {
-            memory: contents.clone(),
+            memory: contents.to_vec(),
             pc: 0,
             input: input.clone(),
             output: Vec::new(),
@@ -108,6 +108,7 @@ impl Intcode {
         }
     }
 
+    #[allow(dead_code)]
     pub fn dump(&self) {
         let contents: Vec<String> = self.memory.iter().map(|e| e.to_string()).collect();
         println!("{}", contents.join(","));
@@ -129,7 +130,7 @@ impl Modes {
     }
 }
 
-fn run_setting(memory: &Vec<i32>, phases: &Vec<i32>) -> i32 {
+fn run_setting(memory: &[i32], phases: &[i32]) -> i32 {
     let mut input = 0;
 
     for phase in phases {
@@ -152,7 +153,7 @@ fn main() -> std::io::Result<()> {
     let memory: Vec<i32> = data
         .trim()
         .split(',')
-        .map(|e| i32::from_str_radix(e, 10).unwrap())
+        .map(|e| e.parse::<i32>().unwrap())
         .collect();
 
     let mut phases = vec![0, 1, 2, 3, 4];
@@ -173,3 +174,24 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::conformance::{self, Feature};
+
+    #[test]
+    fn conformance() {
+        let features = [Feature::Modes, Feature::Io, Feature::Jumps];
+        conformance::check(&features, |case| {
+            let program: Vec<i32> = case.program.iter().map(|&e| e as i32).collect();
+            let input = case.input.iter().map(|&e| e as i32).collect();
+            let mut cpu = Intcode::new(&program, input);
+            cpu.run();
+            (
+                cpu.output.iter().map(|&e| e.into()).collect(),
+                cpu.memory.iter().map(|&e| e.into()).collect(),
+            )
+        });
+    }
+}
@@ -18,11 +18,11 @@ struct Intcode {
 }
 
 impl Intcode {
-    pub fn new(id: u32, contents: &Vec<i32>) -> Self {
+    pub fn new(id: u32, contents: &[i32]) -> Self {
         let (output, input) = channel();
         Intcode {
             id,
-            memory: contents.clone(),
+            memory: contents.to_vec(),
             pc: 0,
             input,
             output,
@@ -158,7 +158,7 @@ impl Modes {
     }
 }
 
-fn run_setting(memory: &Vec<i32>, phases: &Vec<i32>) -> i32 {
+fn run_setting(memory: &[i32], phases: &[i32]) -> i32 {
     let mut unconfigured_cpus = Vec::new();
 
     for i in 0..phases.len() {
@@ -208,7 +208,7 @@ fn main() -> std::io::Result<()> {
     let memory: Vec<i32> = data
         .trim()
         .split(',')
-        .map(|e| i32::from_str_radix(e, 10).unwrap())
+        .map(|e| e.parse::<i32>().unwrap())
         .collect();
 
     let mut phases = vec![5, 6, 7, 8, 9];
@@ -229,3 +229,33 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::conformance::{self, Feature};
+
+    #[test]
+    fn conformance() {
+        let features = [Feature::Modes, Feature::Io, Feature::Jumps];
+        conformance::check(&features, |case| {
+            let program: Vec<i32> = case.program.iter().map(|&e| e as i32).collect();
+            let mut cpu = Intcode::new(0, &program);
+
+            let (input, receiver) = channel();
+            for &value in case.input.iter() {
+                input.send(value as i32).unwrap();
+            }
+            cpu.input = receiver;
+
+            let (sender, output) = channel();
+            cpu.output = sender;
+
+            cpu.run();
+            (
+                output.try_iter().map(|e| e.into()).collect(),
+                cpu.memory.iter().map(|&e| e.into()).collect(),
+            )
+        });
+    }
+}
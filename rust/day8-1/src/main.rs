@@ -1,45 +1,34 @@
 use std::io;
+use std::io::prelude::*;
+use std::process;
 
-fn main() {
-    let width = std::env::args().nth(1).unwrap();
-    let width = u32::from_str_radix(&width, 10).unwrap();
-    let height = std::env::args().nth(2).unwrap();
-    let height = u32::from_str_radix(&height, 10).unwrap();
+use sif::{Image, BLACK, TRANSPARENT, WHITE};
 
-    let stdin = io::stdin();
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let width: usize = args.next().unwrap().parse().unwrap();
+    let height: usize = args.next().unwrap().parse().unwrap();
 
     let mut data = String::new();
+    io::stdin().read_to_string(&mut data)?;
 
-    stdin.read_line(&mut data).unwrap();
-
-    let mut pixels: Vec<u8> = Vec::new();
-
-    for ch in data.trim().chars() {
-        let pixel = u8::from_str_radix(&ch.to_string(), 10).unwrap();
-        pixels.push(pixel);
-    }
-
-    let mut layers: Vec<Vec<u8>> = Vec::new();
-    let layer_size = (width * height) as usize;
-
-    for i in (0..pixels.len()).step_by(layer_size) {
-        let layer = Vec::from(&pixels[i..i + layer_size]);
-        layers.push(layer);
-    }
-
-    let mut zero_layer: Vec<u8> = Vec::new();
-    let mut min_zeroes: usize = 150;
-
-    for layer in layers {
-        let zero_count = layer.iter().filter(|x| **x == 0).count();
-        if zero_count < min_zeroes {
-            zero_layer = layer;
-            min_zeroes = zero_count;
+    let image = match Image::parse(&data, width, height) {
+        Ok(image) => image,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
-    }
+    };
 
-    let ones = zero_layer.iter().filter(|x| **x == 1).count();
-    let twos = zero_layer.iter().filter(|x| **x == 2).count();
+    let histogram = image
+        .layers()
+        .map(|layer| layer.histogram())
+        .min_by_key(|counts| counts[BLACK as usize])
+        .unwrap();
 
+    let ones = histogram[WHITE as usize];
+    let twos = histogram[TRANSPARENT as usize];
     println!("{} * {} = {}", ones, twos, ones * twos);
+
+    Ok(())
 }
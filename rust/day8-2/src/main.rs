@@ -1,55 +1,117 @@
+use std::fs::{self, File};
 use std::io;
+use std::io::prelude::*;
+use std::process;
+use std::time::Duration;
 
-fn main() {
-    let width = std::env::args().nth(1).unwrap();
-    let width = u32::from_str_radix(&width, 10).unwrap();
-    let height = std::env::args().nth(2).unwrap();
-    let height = u32::from_str_radix(&height, 10).unwrap();
+use ocr::Bitmap;
+use sif::export::{self, Palette};
+use sif::steps;
+use sif::{Image, WHITE};
 
-    let stdin = io::stdin();
-
-    let mut data = String::new();
+fn usage() -> ! {
+    eprintln!("usage: day8-2 WIDTH HEIGHT [--format letters|text|steps|blocks|pbm|pgm|png]\n              [--output FILE] [--scale N] [--palette COLOURS] [--gif FILE]\n              [--apng FILE] [--frames DIR] [--delay MS]");
+    process::exit(2);
+}
 
-    stdin.read_line(&mut data).unwrap();
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let width: usize = args.next().unwrap().parse().unwrap();
+    let height: usize = args.next().unwrap().parse().unwrap();
 
-    let mut pixels: Vec<u8> = Vec::new();
+    let mut format = String::from("letters");
+    let mut output = None;
+    let mut scale = 1;
+    let mut palette = Palette::default();
+    let mut gif = None;
+    let mut apng = None;
+    let mut frames = None;
+    let mut delay = 200;
 
-    for ch in data.trim().chars() {
-        let pixel = u8::from_str_radix(&ch.to_string(), 10).unwrap();
-        pixels.push(pixel);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = args.next().unwrap(),
+            "--output" => output = Some(args.next().unwrap()),
+            "--scale" => {
+                scale = match args.next().map(|n| n.parse()) {
+                    Some(Ok(n)) if n >= 1 => n,
+                    _ => {
+                        eprintln!("--scale needs a whole number, at least 1");
+                        usage();
+                    }
+                }
+            }
+            "--palette" => {
+                palette = Palette::parse(&args.next().unwrap()).unwrap_or_else(|e| panic!("{}", e))
+            }
+            "--gif" => gif = Some(args.next().unwrap()),
+            "--apng" => apng = Some(args.next().unwrap()),
+            "--frames" => frames = Some(args.next().unwrap()),
+            "--delay" => delay = args.next().unwrap().parse().unwrap(),
+            x => panic!("unknown option: {}", x),
+        }
     }
 
-    let mut layers: Vec<Vec<u8>> = Vec::new();
-    let layer_size = (width * height) as usize;
-
-    for i in (0..pixels.len()).step_by(layer_size) {
-        let layer = Vec::from(&pixels[i..i + layer_size]);
-        layers.push(layer);
-    }
+    let mut data = String::new();
+    io::stdin().read_to_string(&mut data)?;
 
-    let mut image: Vec<u8> = Vec::new();
-
-    for y in 0..height {
-        for x in 0..width {
-            let pixel = layers
-                .iter()
-                .map(|l| l[(width * y + x) as usize])
-                .skip_while(|p| *p == 2)
-                .next()
-                .unwrap();
-            image.push(pixel);
+    let layers = match Image::parse(&data, width, height) {
+        Ok(image) => image,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
         }
-    }
+    };
+    let image = layers.composite();
+    let layer = image.layer(0).unwrap();
 
-    for (i, pixel) in image.iter().enumerate() {
-        match pixel {
-            0 => print!("#"),
-            1 => print!(" "),
-            _ => (),
-        };
+    let steps = steps::steps(&layers);
+    let delay = Duration::from_millis(delay);
+    if let Some(path) = gif {
+        steps::gif(&steps, delay, scale, &palette, File::create(path)?)?;
+    }
+    if let Some(path) = apng {
+        steps::apng(&steps, delay, scale, &palette, File::create(path)?)?;
+    }
+    if let Some(dir) = frames {
+        fs::create_dir_all(&dir)?;
+        steps::frames(&steps, scale, &palette, dir)?;
+    }
 
-        if (i as u32) % width == width - 1 {
-            println!("");
+    let picture = match format.as_str() {
+        "letters" => {
+            let bitmap = Bitmap::new(layer.width(), layer.height(), |x, y| {
+                layer.get(x, y) == WHITE
+            });
+            match ocr::read(&bitmap) {
+                Ok(letters) => format!("{}\n", letters).into_bytes(),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            }
         }
+        "text" => export::text(&layer).into_bytes(),
+        "steps" => steps
+            .iter()
+            .map(|step| steps::ansi(step, scale, &palette))
+            .collect::<String>()
+            .into_bytes(),
+        "blocks" => export::blocks(&layer, scale).into_bytes(),
+        "pbm" => export::pbm(&layer, scale).into_bytes(),
+        "pgm" => export::pgm(&layer, scale, &palette).into_bytes(),
+        "png" => {
+            let mut png = Vec::new();
+            export::png(&layer, scale, &palette, &mut png)?;
+            png
+        }
+        x => panic!("unknown format: {}", x),
+    };
+
+    match output {
+        Some(path) => fs::write(path, picture)?,
+        None => io::stdout().write_all(&picture)?,
     }
+
+    Ok(())
 }
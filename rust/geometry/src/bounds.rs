@@ -0,0 +1,72 @@
+use crate::point::Point;
+use crate::Coord;
+
+/// A box with both corners inside it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    /// Top left.
+    pub min: Point,
+    /// Bottom right.
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(min: Point, max: Point) -> Self {
+        Bounds { min, max }
+    }
+
+    /// The smallest box holding every point, or `None` if there aren't
+    /// any.
+    pub fn around<I: IntoIterator<Item = Point>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Bounds::new(first, first);
+
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the box to take in `point`.
+    pub fn include(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    /// Where `point` falls counting row by row from the top left.
+    pub fn index(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            let offset = point - self.min;
+            Some(offset.y as usize * self.width() + offset.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Every point in the box, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn rows(&self) -> std::ops::RangeInclusive<Coord> {
+        self.min.y..=self.max.y
+    }
+
+    pub fn columns(&self) -> std::ops::RangeInclusive<Coord> {
+        self.min.x..=self.max.x
+    }
+}
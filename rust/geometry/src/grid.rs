@@ -0,0 +1,233 @@
+//! Grids of anything. `Sparse` holds only the points that have been set
+//! and suits things that wander off in any direction; `Dense` holds every
+//! point in a fixed box.
+
+use std::collections::hash_map::{self, HashMap};
+use std::iter::FromIterator;
+use std::ops::Index;
+
+use crate::bounds::Bounds;
+use crate::point::Point;
+use crate::Coord;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sparse<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for Sparse<T> {
+    fn default() -> Self {
+        Sparse {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Sparse<T> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Sets a point, returning what was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn entry(&mut self, point: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Every point set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().cloned()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::around(self.points())
+    }
+
+    /// Draws the grid as text, leaving points never set blank and
+    /// trimming the ends of rows.
+    pub fn render<F: Fn(&T) -> char>(&self, glyph: F) -> String {
+        let mut text = String::new();
+
+        if let Some(bounds) = self.bounds() {
+            for y in bounds.rows() {
+                let row: String = bounds
+                    .columns()
+                    .map(|x| self.get(Point::new(x, y)).map_or(' ', &glyph))
+                    .collect();
+                text.push_str(row.trim_end());
+                text.push('\n');
+            }
+        }
+
+        text
+    }
+}
+
+impl<T: Clone> Sparse<T> {
+    /// Fills in the box around everything set, with `fill` for points
+    /// never set.
+    pub fn to_dense(&self, fill: T) -> Option<Dense<T>> {
+        let mut dense = Dense::new(self.bounds()?, fill);
+        for (point, value) in self.iter() {
+            dense.set(point, value.clone());
+        }
+        Some(dense)
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for Sparse<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Sparse {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Point, T)> for Sparse<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter);
+    }
+}
+
+impl<T> Index<Point> for Sparse<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        &self.cells[&point]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dense<T> {
+    bounds: Bounds,
+    /// Row by row from the top left.
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Dense<T> {
+    pub fn new(bounds: Bounds, fill: T) -> Self {
+        Dense {
+            bounds,
+            cells: vec![fill; bounds.width() * bounds.height()],
+        }
+    }
+}
+
+impl<T> Dense<T> {
+    /// Reads a picture like the puzzle inputs, one row per line with the
+    /// top left at the origin. Short lines are an error.
+    pub fn parse<F: Fn(char) -> T>(text: &str, cell: F) -> Result<Self, String> {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (n, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!("line {}: expected {} columns", n + 1, width));
+            }
+            cells.extend(line.chars().map(&cell));
+        }
+
+        Ok(Dense {
+            bounds: Bounds::new(
+                Point::ORIGIN,
+                Point::new(width as Coord - 1, lines.len() as Coord - 1),
+            ),
+            cells,
+        })
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.height()
+    }
+
+    /// `None` outside the box.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.bounds.index(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.bounds.index(point).map(move |i| &mut self.cells[i])
+    }
+
+    /// Sets a point inside the box, panicking outside it.
+    pub fn set(&mut self, point: Point, value: T) {
+        match self.bounds.index(point) {
+            Some(i) => self.cells[i] = value,
+            None => panic!("{} is outside {:?}", point, self.bounds),
+        }
+    }
+
+    /// Every point with its value, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.bounds.points().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width().max(1))
+    }
+
+    /// Draws the whole box as text, a line per row.
+    pub fn render<F: Fn(&T) -> char>(&self, glyph: F) -> String {
+        let mut text = String::with_capacity((self.width() + 1) * self.height());
+
+        for row in self.rows() {
+            text.extend(row.iter().map(&glyph));
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<T> Index<Point> for Dense<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is outside {:?}", point, self.bounds))
+    }
+}
@@ -0,0 +1,142 @@
+use crate::point::{Point, Vector};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Heading {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+}
+
+impl Turn {
+    /// How many quarter turns clockwise.
+    fn quarters(self) -> usize {
+        match self {
+            Turn::Right => 1,
+            Turn::Around => 2,
+            Turn::Left => 3,
+        }
+    }
+}
+
+impl Heading {
+    /// Clockwise from up.
+    pub const ALL: [Heading; 4] = [Heading::Up, Heading::Right, Heading::Down, Heading::Left];
+
+    /// `U`, `R`, `D` or `L`, as in day 3's wire paths.
+    pub fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'U' => Some(Heading::Up),
+            'R' => Some(Heading::Right),
+            'D' => Some(Heading::Down),
+            'L' => Some(Heading::Left),
+            _ => None,
+        }
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        Heading::ALL[(self as usize + turn.quarters()) % 4]
+    }
+
+    pub fn delta(self) -> Vector {
+        match self {
+            Heading::Up => Point::new(0, -1),
+            Heading::Right => Point::new(1, 0),
+            Heading::Down => Point::new(0, 1),
+            Heading::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// The position one step from `pos` this way.
+    pub fn step(self, pos: Point) -> Point {
+        pos + self.delta()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Heading::Up => "up",
+            Heading::Right => "right",
+            Heading::Down => "down",
+            Heading::Left => "left",
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Heading::Up => '^',
+            Heading::Right => '>',
+            Heading::Down => 'v',
+            Heading::Left => '<',
+        }
+    }
+}
+
+/// Headings that take in the diagonals too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Heading8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Heading8 {
+    /// Clockwise from up.
+    pub const ALL: [Heading8; 8] = [
+        Heading8::Up,
+        Heading8::UpRight,
+        Heading8::Right,
+        Heading8::DownRight,
+        Heading8::Down,
+        Heading8::DownLeft,
+        Heading8::Left,
+        Heading8::UpLeft,
+    ];
+
+    /// Turns by `eighths` of a full turn, clockwise if positive.
+    pub fn rotate(self, eighths: i32) -> Self {
+        Heading8::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        self.rotate(turn.quarters() as i32 * 2)
+    }
+
+    pub fn delta(self) -> Vector {
+        match self {
+            Heading8::Up => Point::new(0, -1),
+            Heading8::UpRight => Point::new(1, -1),
+            Heading8::Right => Point::new(1, 0),
+            Heading8::DownRight => Point::new(1, 1),
+            Heading8::Down => Point::new(0, 1),
+            Heading8::DownLeft => Point::new(-1, 1),
+            Heading8::Left => Point::new(-1, 0),
+            Heading8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    pub fn step(self, pos: Point) -> Point {
+        pos + self.delta()
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Heading> for Heading8 {
+    fn from(heading: Heading) -> Self {
+        Heading8::ALL[heading as usize * 2]
+    }
+}
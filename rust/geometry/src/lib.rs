@@ -0,0 +1,21 @@
+//! Points, headings and grids on the integer plane, shared by the puzzles
+//! that wander around one.
+//!
+//! `y` grows downwards, so `Up` takes `y` down by one.
+
+pub mod bounds;
+pub mod grid;
+pub mod heading;
+pub mod moves;
+pub mod point;
+pub mod polyline;
+pub mod segment;
+
+pub use crate::bounds::Bounds;
+pub use crate::grid::{Dense, Sparse};
+pub use crate::heading::{Heading, Heading8, Turn};
+pub use crate::point::{Point, Vector};
+pub use crate::polyline::Polyline;
+pub use crate::segment::Segment;
+
+pub type Coord = i64;
@@ -0,0 +1,166 @@
+//! Paths written out as moves, like day 3's `R8,U5,L5,D3`: one path per
+//! line, each move a direction and a distance, separated by commas.
+//!
+//! With diagonals turned on, `UR`, `UL`, `DR` and `DL` move both ways at
+//! once, one step for each point along the diagonal.
+
+use std::error;
+use std::fmt;
+
+use crate::heading::Heading8;
+use crate::point::Point;
+use crate::Coord;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub heading: Heading8,
+    pub distance: Coord,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Nothing between two commas, or after the last one.
+    Empty,
+    UnknownDirection(String),
+    /// A diagonal when they're turned off.
+    Diagonal(String),
+    MissingDistance,
+    /// Something other than digits where the distance should be.
+    BadDistance(String),
+    ZeroDistance,
+    /// A distance too big to hold, or one that takes the path past the
+    /// edge of the plane.
+    Overflow,
+}
+
+/// What went wrong and where, counting lines and columns from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::Empty => write!(f, "empty move"),
+            ErrorKind::UnknownDirection(d) => write!(f, "unknown direction {:?}", d),
+            ErrorKind::Diagonal(d) => write!(f, "diagonal move {:?} isn't allowed", d),
+            ErrorKind::MissingDistance => write!(f, "missing distance"),
+            ErrorKind::BadDistance(d) => write!(f, "bad distance {:?}", d),
+            ErrorKind::ZeroDistance => write!(f, "zero-length move"),
+            ErrorKind::Overflow => write!(f, "distance overflows"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Parser {
+    diagonals: bool,
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Allows `UR`, `UL`, `DR` and `DL`.
+    pub fn diagonals(mut self, diagonals: bool) -> Self {
+        self.diagonals = diagonals;
+        self
+    }
+
+    /// One list of moves per line. Blank lines are skipped, and spaces
+    /// around moves are fine.
+    pub fn parse(&self, text: &str) -> Result<Vec<Vec<Move>>, Error> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                self.line(line).map_err(|(column, kind)| Error {
+                    line: n + 1,
+                    column,
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    /// The moves on one line, or the column something went wrong at.
+    fn line(&self, line: &str) -> Result<Vec<Move>, (usize, ErrorKind)> {
+        let mut moves = Vec::new();
+        let mut pos = Point::ORIGIN;
+        let mut column = 1;
+
+        for word in line.split(',') {
+            let start = column + word.chars().take_while(|c| c.is_whitespace()).count();
+            column += word.chars().count() + 1;
+
+            let m = self
+                .word(word.trim())
+                .map_err(|(at, kind)| (start + at, kind))?;
+            pos = advance(pos, m).ok_or((start, ErrorKind::Overflow))?;
+            moves.push(m);
+        }
+
+        Ok(moves)
+    }
+
+    /// One move, or how far into it something went wrong.
+    fn word(&self, word: &str) -> Result<Move, (usize, ErrorKind)> {
+        if word.is_empty() {
+            return Err((0, ErrorKind::Empty));
+        }
+
+        let letters: String = word.chars().take_while(|c| c.is_alphabetic()).collect();
+        let heading = match letters.as_str() {
+            "" => {
+                return Err((
+                    0,
+                    ErrorKind::UnknownDirection(word.chars().take(1).collect()),
+                ))
+            }
+            "U" => Heading8::Up,
+            "R" => Heading8::Right,
+            "D" => Heading8::Down,
+            "L" => Heading8::Left,
+            "UR" | "UL" | "DR" | "DL" if !self.diagonals => {
+                return Err((0, ErrorKind::Diagonal(letters)))
+            }
+            "UR" => Heading8::UpRight,
+            "UL" => Heading8::UpLeft,
+            "DR" => Heading8::DownRight,
+            "DL" => Heading8::DownLeft,
+            _ => return Err((0, ErrorKind::UnknownDirection(letters))),
+        };
+
+        let at = letters.chars().count();
+        let digits = &word[letters.len()..];
+        if digits.is_empty() {
+            return Err((at, ErrorKind::MissingDistance));
+        }
+        if let Some(bad) = digits.chars().position(|c| !c.is_ascii_digit()) {
+            return Err((at + bad, ErrorKind::BadDistance(digits.to_string())));
+        }
+
+        let distance: Coord = digits.parse().map_err(|_| (at, ErrorKind::Overflow))?;
+        if distance == 0 {
+            return Err((at, ErrorKind::ZeroDistance));
+        }
+
+        Ok(Move { heading, distance })
+    }
+}
+
+/// Where a move from `pos` ends up, unless it's off the edge.
+fn advance(pos: Point, m: Move) -> Option<Point> {
+    let delta = m.heading.delta();
+    Some(Point::new(
+        pos.x.checked_add(delta.x.checked_mul(m.distance)?)?,
+        pos.y.checked_add(delta.y.checked_mul(m.distance)?)?,
+    ))
+}
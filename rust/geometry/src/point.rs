@@ -0,0 +1,134 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::heading::{Heading, Heading8};
+use crate::Coord;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: Coord,
+    pub y: Coord,
+}
+
+/// The difference between two points. Same thing, different intent.
+pub type Vector = Point;
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: Coord, y: Coord) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> Coord {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Moves between the points when diagonal moves count as one.
+    pub fn chebyshev(self, other: Point) -> Coord {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn euclidean(self, other: Point) -> f64 {
+        let d = other - self;
+        (d.x as f64).hypot(d.y as f64)
+    }
+
+    /// The vector divided through by the greatest common divisor of its
+    /// parts, so every vector pointing the same way reduces to the same
+    /// one.
+    pub fn reduced(self) -> Vector {
+        let (mut a, mut b) = (self.x.abs(), self.y.abs());
+        while b != 0 {
+            let t = a % b;
+            a = b;
+            b = t;
+        }
+
+        if a == 0 {
+            self
+        } else {
+            Point::new(self.x / a, self.y / a)
+        }
+    }
+
+    /// The four points sharing an edge with this one, clockwise from up.
+    pub fn neighbours(self) -> [Point; 4] {
+        let mut points = [self; 4];
+        for (point, heading) in points.iter_mut().zip(&Heading::ALL) {
+            *point = heading.step(self);
+        }
+        points
+    }
+
+    /// The eight points around this one, clockwise from up.
+    pub fn surrounding(self) -> [Point; 8] {
+        let mut points = [self; 8];
+        for (point, heading) in points.iter_mut().zip(&Heading8::ALL) {
+            *point = heading.step(self);
+        }
+        points
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl From<(Coord, Coord)> for Point {
+    fn from((x, y): (Coord, Coord)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for (Coord, Coord) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<Coord> for Point {
+    type Output = Point;
+
+    fn mul(self, n: Coord) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
@@ -0,0 +1,146 @@
+//! Paths walked as a series of straight runs, like day 3's wires, and the
+//! points where different paths cross.
+
+use std::collections::BTreeMap;
+
+use crate::heading::Heading8;
+use crate::moves::Move;
+use crate::point::Point;
+use crate::segment::{self, Segment};
+use crate::Coord;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polyline {
+    start: Point,
+    segments: Vec<Segment>,
+    /// Steps along the path to the start of each segment.
+    steps: Vec<Coord>,
+}
+
+impl Polyline {
+    pub fn new(start: Point) -> Self {
+        Polyline {
+            start,
+            segments: Vec::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn from_runs<H, I>(start: Point, runs: I) -> Self
+    where
+        H: Into<Heading8>,
+        I: IntoIterator<Item = (H, Coord)>,
+    {
+        let mut path = Polyline::new(start);
+        for (heading, distance) in runs {
+            path.push(heading, distance);
+        }
+        path
+    }
+
+    pub fn from_moves(start: Point, moves: &[Move]) -> Self {
+        Polyline::from_runs(start, moves.iter().map(|m| (m.heading, m.distance)))
+    }
+
+    /// Walks on from the end of the path. Diagonal moves take one step
+    /// each.
+    pub fn push<H: Into<Heading8>>(&mut self, heading: H, distance: Coord) {
+        let from = self.end();
+        self.steps.push(self.len());
+        self.segments
+            .push(Segment::new(from, from + heading.into().delta() * distance));
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    pub fn end(&self) -> Point {
+        self.segments.last().map_or(self.start, |s| s.end)
+    }
+
+    /// Steps from one end to the other.
+    pub fn len(&self) -> Coord {
+        match (self.segments.last(), self.steps.last()) {
+            (Some(segment), Some(steps)) => steps + segment.length(),
+            _ => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Steps along the path to where it first reaches `point`.
+    pub fn steps_to(&self, point: Point) -> Option<Coord> {
+        if point == self.start {
+            return Some(0);
+        }
+
+        self.segments
+            .iter()
+            .zip(&self.steps)
+            .find(|(segment, _)| segment.contains(point))
+            .map(|(segment, steps)| steps + segment.start.chebyshev(point))
+    }
+}
+
+/// A point reached by more than one path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crossing {
+    pub point: Point,
+    /// Manhattan distance from the origin.
+    pub distance: Coord,
+    /// The fewest steps each path takes to get here, added up.
+    pub steps: Coord,
+    /// Each path that reaches the point, by index, with the fewest steps
+    /// it takes to get there.
+    pub paths: Vec<(usize, Coord)>,
+}
+
+/// Every point that two or more of the paths share, nearest the origin
+/// first, with ties going to the fewest steps. A path crossing itself
+/// doesn't count, and paths running along each other cross at every
+/// point they share.
+pub fn crossings(paths: &[Polyline]) -> Vec<Crossing> {
+    let mut segments = Vec::new();
+    // The path and step count at the start of each segment.
+    let mut owners = Vec::new();
+
+    for (n, path) in paths.iter().enumerate() {
+        segments.extend_from_slice(&path.segments);
+        owners.extend(path.steps.iter().map(|&steps| (n, steps)));
+    }
+
+    let mut reached: BTreeMap<Point, BTreeMap<usize, Coord>> = BTreeMap::new();
+    for hit in segment::intersections(&segments) {
+        let (first, second) = (owners[hit.first], owners[hit.second]);
+        if first.0 == second.0 {
+            continue;
+        }
+
+        let paths = reached.entry(hit.point).or_default();
+        for &(i, (path, steps)) in &[(hit.first, first), (hit.second, second)] {
+            let steps = steps + segments[i].start.chebyshev(hit.point);
+            let fewest = paths.entry(path).or_insert(steps);
+            *fewest = steps.min(*fewest);
+        }
+    }
+
+    let mut crossings: Vec<Crossing> = reached
+        .into_iter()
+        .map(|(point, paths)| Crossing {
+            point,
+            distance: point.manhattan(Point::ORIGIN),
+            steps: paths.values().sum(),
+            paths: paths.into_iter().collect(),
+        })
+        .collect();
+
+    crossings.sort_by_key(|c| (c.distance, c.steps, c.point.y, c.point.x));
+    crossings
+}
@@ -0,0 +1,247 @@
+//! Straight runs between two points, and a sweep line to find where a lot
+//! of them meet without trying every pair.
+
+use std::collections::BTreeMap;
+
+use crate::bounds::Bounds;
+use crate::point::{Point, Vector};
+use crate::Coord;
+
+/// A horizontal, vertical or diagonal run, with both ends on it. A single
+/// point counts as horizontal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    /// Panics if the ends don't share a row, a column or a diagonal.
+    pub fn new(start: Point, end: Point) -> Self {
+        let d = end - start;
+        if d.x != 0 && d.y != 0 && d.x.abs() != d.y.abs() {
+            panic!("{} to {} isn't straight", start, end);
+        }
+        Segment { start, end }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x && !self.is_horizontal()
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        !self.is_horizontal() && !self.is_vertical()
+    }
+
+    /// The step from each point to the next, or nothing for a single
+    /// point.
+    pub fn direction(&self) -> Vector {
+        let d = self.end - self.start;
+        Point::new(d.x.signum(), d.y.signum())
+    }
+
+    /// Steps from one end to the other.
+    pub fn length(&self) -> Coord {
+        self.start.chebyshev(self.end)
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::new(self.start, self.start);
+        bounds.include(self.end);
+        bounds
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        let d = point - self.start;
+        self.bounds().contains(point) && (!self.is_diagonal() || d.x.abs() == d.y.abs())
+    }
+
+    /// Every point on the segment, from start to end.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (start, step) = (self.start, self.direction());
+        (0..=self.length()).map(move |n| start + step * n)
+    }
+
+    /// Where this segment and `other` meet, for any two segments. The
+    /// sweep only needs this when a diagonal's involved.
+    pub fn meet(&self, other: &Segment) -> Vec<Point> {
+        let (d1, d2) = (self.direction(), other.direction());
+        let r = other.start - self.start;
+        let cross = |a: Vector, b: Vector| a.x * b.y - a.y * b.x;
+
+        if d1 == Point::ORIGIN {
+            return if other.contains(self.start) {
+                vec![self.start]
+            } else {
+                vec![]
+            };
+        }
+        if d2 == Point::ORIGIN {
+            return other.meet(self);
+        }
+
+        let det = cross(d1, d2);
+        if det == 0 {
+            // Parallel, so only a shared line will do.
+            if cross(r, d1) != 0 {
+                return vec![];
+            }
+            // How far along this segment each end of the other one is.
+            let along = |p: Point| {
+                let d = p - self.start;
+                if d1.x != 0 {
+                    d.x / d1.x
+                } else {
+                    d.y / d1.y
+                }
+            };
+            let (a, b) = (along(other.start), along(other.end));
+            let from = a.min(b).max(0);
+            let to = a.max(b).min(self.length());
+            return (from..=to).map(|t| self.start + d1 * t).collect();
+        }
+
+        let (t, u) = (cross(r, d2), cross(r, d1));
+        if t % det != 0 || u % det != 0 {
+            // They cross between points.
+            return vec![];
+        }
+        let (t, u) = (t / det, u / det);
+        if (0..=self.length()).contains(&t) && (0..=other.length()).contains(&u) {
+            vec![self.start + d1 * t]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// A point shared by two segments, by their indices, lower first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Intersection {
+    pub first: usize,
+    pub second: usize,
+    pub point: Point,
+}
+
+impl Intersection {
+    fn new(a: usize, b: usize, point: Point) -> Self {
+        Intersection {
+            first: a.min(b),
+            second: a.max(b),
+            point,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    // In this order at any one x, so verticals there see segments that
+    // start or end there too.
+    Start,
+    Vertical,
+    End,
+}
+
+/// Every point where two segments meet, including segments that touch
+/// end to end. Segments that overlap along a line meet at every point of
+/// the overlap.
+///
+/// Sweeps across from left to right, keeping the horizontal segments
+/// under the line by row, so each vertical only looks at the rows it
+/// spans. That's `O((n + k) log n)` for `n` segments meeting at `k`
+/// points. Diagonals are kept aside and checked against everything else
+/// under the line with them, which is fine while there aren't many.
+pub fn intersections(segments: &[Segment]) -> Vec<Intersection> {
+    let mut events = Vec::with_capacity(segments.len() * 2);
+    for (i, segment) in segments.iter().enumerate() {
+        let bounds = segment.bounds();
+        if segment.is_vertical() {
+            events.push((bounds.min.x, Event::Vertical, i));
+        } else {
+            events.push((bounds.min.x, Event::Start, i));
+            events.push((bounds.max.x, Event::End, i));
+        }
+    }
+    events.sort();
+
+    let mut found = Vec::new();
+    // Horizontals the line is crossing, by row.
+    let mut active: BTreeMap<Coord, Vec<usize>> = BTreeMap::new();
+    // Diagonals the line is crossing.
+    let mut diagonals: Vec<usize> = Vec::new();
+    // Verticals seen so far on the line, for the ones that overlap.
+    let mut column: Vec<usize> = Vec::new();
+    let mut column_x = None;
+
+    for (x, event, i) in events {
+        let segment = &segments[i];
+        let bounds = segment.bounds();
+
+        if event != Event::End {
+            for &j in &diagonals {
+                for point in segment.meet(&segments[j]) {
+                    found.push(Intersection::new(i, j, point));
+                }
+            }
+        }
+
+        match event {
+            Event::Start if segment.is_diagonal() => {
+                for row in active
+                    .range(bounds.min.y..=bounds.max.y)
+                    .map(|(_, row)| row)
+                {
+                    for &j in row {
+                        for point in segment.meet(&segments[j]) {
+                            found.push(Intersection::new(i, j, point));
+                        }
+                    }
+                }
+                diagonals.push(i);
+            }
+            Event::Start => {
+                let row = active.entry(bounds.min.y).or_default();
+                for &j in row.iter() {
+                    let end = bounds.max.x.min(segments[j].bounds().max.x);
+                    for x in x..=end {
+                        found.push(Intersection::new(i, j, Point::new(x, bounds.min.y)));
+                    }
+                }
+                row.push(i);
+            }
+            Event::Vertical => {
+                for (&y, row) in active.range(bounds.min.y..=bounds.max.y) {
+                    for &j in row {
+                        found.push(Intersection::new(i, j, Point::new(x, y)));
+                    }
+                }
+
+                if column_x != Some(x) {
+                    column.clear();
+                    column_x = Some(x);
+                }
+                for &j in &column {
+                    let other = segments[j].bounds();
+                    for y in bounds.min.y.max(other.min.y)..=bounds.max.y.min(other.max.y) {
+                        found.push(Intersection::new(i, j, Point::new(x, y)));
+                    }
+                }
+                column.push(i);
+            }
+            Event::End if segment.is_diagonal() => diagonals.retain(|&j| j != i),
+            Event::End => {
+                let row = active.get_mut(&bounds.min.y).unwrap();
+                row.retain(|&j| j != i);
+                if row.is_empty() {
+                    active.remove(&bounds.min.y);
+                }
+            }
+        }
+    }
+
+    found
+}
@@ -0,0 +1,57 @@
+use geometry::{Bounds, Dense, Point, Sparse};
+
+#[test]
+fn sparse() {
+    let mut grid = Sparse::new();
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.render(|_: &u8| '#'), "");
+
+    assert_eq!(grid.insert(Point::new(2, 0), 1), None);
+    assert_eq!(grid.insert(Point::new(0, 1), 2), None);
+    assert_eq!(grid.insert(Point::new(2, 0), 3), Some(1));
+    assert_eq!(grid.len(), 2);
+    assert_eq!(grid[Point::new(2, 0)], 3);
+    assert_eq!(grid.get(Point::new(1, 1)), None);
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds::new(Point::new(0, 0), Point::new(2, 1)))
+    );
+
+    // Rows are trimmed.
+    assert_eq!(grid.render(|&v| (b'0' + v) as char), "  3\n2\n");
+}
+
+#[test]
+fn dense() {
+    let grid = Dense::parse(".#.\n#..\n", |c| c == '#').unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert!(grid[Point::new(1, 0)]);
+    assert!(!grid[Point::new(1, 1)]);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(
+        grid.iter()
+            .filter(|&(_, &v)| v)
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>(),
+        vec![Point::new(1, 0), Point::new(0, 1)]
+    );
+    assert_eq!(grid.render(|&v| if v { '#' } else { '.' }), ".#.\n#..\n");
+
+    assert_eq!(
+        Dense::parse("..\n.\n", |c| c).unwrap_err(),
+        "line 2: expected 2 columns"
+    );
+}
+
+#[test]
+fn sparse_to_dense() {
+    let grid: Sparse<char> = vec![(Point::new(-1, -1), 'a'), (Point::new(1, 0), 'b')]
+        .into_iter()
+        .collect();
+    let mut dense = grid.to_dense('.').unwrap();
+    assert_eq!(dense.bounds().min, Point::new(-1, -1));
+    assert_eq!(dense.render(|&c| c), "a..\n..b\n");
+
+    dense.set(Point::new(0, 0), 'c');
+    assert_eq!(dense.render(|&c| c), "a..\n.cb\n");
+}
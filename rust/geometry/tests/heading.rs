@@ -0,0 +1,29 @@
+use geometry::{Heading, Heading8, Point, Turn};
+
+#[test]
+fn turns() {
+    assert_eq!(Heading::Up.turn(Turn::Left), Heading::Left);
+    assert_eq!(Heading::Left.turn(Turn::Right), Heading::Up);
+    assert_eq!(Heading::Right.turn(Turn::Around), Heading::Left);
+    assert_eq!(Heading::Down.step(Point::new(3, 4)), Point::new(3, 5));
+    assert_eq!(Heading::from_letter('L'), Some(Heading::Left));
+    assert_eq!(Heading::from_letter('x'), None);
+}
+
+#[test]
+fn eight_way() {
+    assert_eq!(Heading8::Up.rotate(1), Heading8::UpRight);
+    assert_eq!(Heading8::Up.rotate(-1), Heading8::UpLeft);
+    assert_eq!(Heading8::DownLeft.rotate(11), Heading8::Up);
+    assert_eq!(Heading8::UpRight.turn(Turn::Right), Heading8::DownRight);
+    assert_eq!(Heading8::UpRight.turn(Turn::Around), Heading8::DownLeft);
+    assert_eq!(Heading8::from(Heading::Left), Heading8::Left);
+    assert!(Heading8::DownRight.is_diagonal());
+    assert_eq!(Heading8::UpLeft.step(Point::ORIGIN), Point::new(-1, -1));
+
+    // Each diagonal is the sum of the headings either side of it.
+    for (n, heading) in Heading8::ALL.iter().enumerate().filter(|(n, _)| n % 2 == 1) {
+        let (before, after) = (Heading8::ALL[n - 1], Heading8::ALL[(n + 1) % 8]);
+        assert_eq!(heading.delta(), before.delta() + after.delta());
+    }
+}
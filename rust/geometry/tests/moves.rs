@@ -0,0 +1,90 @@
+use geometry::moves::{Error, ErrorKind, Move, Parser};
+use geometry::{Heading8, Point, Polyline};
+
+fn error(text: &str) -> Error {
+    Parser::new().parse(text).unwrap_err()
+}
+
+#[test]
+fn parses_wires() {
+    let wires = Parser::new().parse("R8,U5,L5,D3\n\n U7 , R6\n").unwrap();
+    assert_eq!(wires.len(), 2);
+    assert_eq!(
+        wires[0][..2],
+        [
+            Move {
+                heading: Heading8::Right,
+                distance: 8
+            },
+            Move {
+                heading: Heading8::Up,
+                distance: 5
+            }
+        ]
+    );
+    assert_eq!(wires[1].len(), 2);
+    assert_eq!(
+        Polyline::from_moves(Point::ORIGIN, &wires[1]).end(),
+        Point::new(6, -7)
+    );
+}
+
+#[test]
+fn errors_say_where() {
+    let e = error("R8,U5\nR1,X5");
+    assert_eq!((e.line, e.column), (2, 4));
+    assert_eq!(e.kind, ErrorKind::UnknownDirection("X".to_string()));
+    assert_eq!(e.to_string(), "line 2, column 4: unknown direction \"X\"");
+
+    let e = error("R8,  U");
+    assert_eq!(
+        (e.line, e.column, e.kind),
+        (1, 7, ErrorKind::MissingDistance)
+    );
+
+    let e = error("R8,U5x");
+    assert_eq!(
+        (e.line, e.column, e.kind),
+        (1, 6, ErrorKind::BadDistance("5x".to_string()))
+    );
+
+    assert_eq!(error("R0").kind, ErrorKind::ZeroDistance);
+    assert_eq!(error("R8,,U1").column, 4);
+    assert_eq!(error("R8,").kind, ErrorKind::Empty);
+    assert_eq!(
+        error("7").kind,
+        ErrorKind::UnknownDirection("7".to_string())
+    );
+}
+
+#[test]
+fn overflow() {
+    let e = error("R99999999999999999999");
+    assert_eq!((e.column, e.kind), (2, ErrorKind::Overflow));
+
+    // Each move fits, but not both together.
+    let e = error("R9223372036854775807,R1");
+    assert_eq!((e.column, e.kind), (22, ErrorKind::Overflow));
+}
+
+#[test]
+fn diagonals_are_optional() {
+    let e = error("R2,UR3");
+    assert_eq!(
+        (e.column, e.kind),
+        (4, ErrorKind::Diagonal("UR".to_string()))
+    );
+
+    let wires = Parser::new().diagonals(true).parse("R2,UR3,DL1").unwrap();
+    assert_eq!(wires[0][1].heading, Heading8::UpRight);
+
+    let wire = Polyline::from_moves(Point::ORIGIN, &wires[0]);
+    assert_eq!(wire.end(), Point::new(4, -2));
+    assert_eq!(wire.len(), 6);
+    assert_eq!(wire.steps_to(Point::new(4, -2)), Some(4));
+
+    assert_eq!(
+        error("RU2").kind,
+        ErrorKind::UnknownDirection("RU".to_string())
+    );
+}
@@ -0,0 +1,67 @@
+use geometry::{Bounds, Point};
+
+#[test]
+fn arithmetic() {
+    let mut p = Point::new(3, -4);
+    assert_eq!(p + Point::new(1, 1), Point::new(4, -3));
+    assert_eq!(p - Point::new(1, 1), Point::new(2, -5));
+    assert_eq!(-p, Point::new(-3, 4));
+    assert_eq!(p * 3, Point::new(9, -12));
+
+    p += Point::new(-3, 4);
+    assert_eq!(p, Point::ORIGIN);
+    p -= Point::new(1, 2);
+    assert_eq!(<(i64, i64)>::from(p), (-1, -2));
+    assert_eq!(Point::from((5, 6)).to_string(), "5,6");
+}
+
+#[test]
+fn distances() {
+    let p = Point::new(3, -4);
+    assert_eq!(p.manhattan(Point::ORIGIN), 7);
+    assert_eq!(Point::ORIGIN.manhattan(p), 7);
+    assert_eq!(p.euclidean(Point::ORIGIN), 5.0);
+    assert_eq!(p.chebyshev(Point::ORIGIN), 4);
+}
+
+#[test]
+fn reduced_vectors_share_a_direction() {
+    assert_eq!(Point::new(6, -9).reduced(), Point::new(2, -3));
+    assert_eq!(Point::new(0, 5).reduced(), Point::new(0, 1));
+    assert_eq!(Point::new(-4, 0).reduced(), Point::new(-1, 0));
+    assert_eq!(Point::ORIGIN.reduced(), Point::ORIGIN);
+}
+
+#[test]
+fn neighbours() {
+    let p = Point::new(1, 1);
+    assert_eq!(
+        p.neighbours(),
+        [
+            Point::new(1, 0),
+            Point::new(2, 1),
+            Point::new(1, 2),
+            Point::new(0, 1)
+        ]
+    );
+    assert_eq!(p.surrounding()[1], Point::new(2, 0));
+    assert_eq!(p.surrounding()[7], Point::new(0, 0));
+}
+
+#[test]
+fn bounds() {
+    let points = vec![Point::new(2, -1), Point::new(-1, 3), Point::new(0, 0)];
+    let mut bounds = Bounds::around(points).unwrap();
+    assert_eq!(bounds, Bounds::new(Point::new(-1, -1), Point::new(2, 3)));
+    assert_eq!((bounds.width(), bounds.height()), (4, 5));
+    assert!(bounds.contains(Point::new(2, 3)));
+    assert!(!bounds.contains(Point::new(3, 3)));
+    assert_eq!(bounds.index(Point::new(-1, -1)), Some(0));
+    assert_eq!(bounds.index(Point::new(0, 0)), Some(5));
+    assert_eq!(bounds.index(Point::new(0, 4)), None);
+
+    bounds.include(Point::new(5, 0));
+    assert_eq!(bounds.max, Point::new(5, 3));
+    assert_eq!(bounds.points().count(), 7 * 5);
+    assert_eq!(Bounds::around(vec![]), None);
+}
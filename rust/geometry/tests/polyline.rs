@@ -0,0 +1,114 @@
+use geometry::polyline::{self, Crossing};
+use geometry::{Heading, Heading8, Point, Polyline};
+
+fn wire(path: &str) -> Polyline {
+    let runs = path.split(',').map(|run| {
+        let heading = Heading::from_letter(run.chars().next().unwrap()).unwrap();
+        (heading, run[1..].parse().unwrap())
+    });
+    Polyline::from_runs(Point::ORIGIN, runs)
+}
+
+/// Crossings other than the origin, where every wire starts.
+fn crossings(paths: &[&str]) -> Vec<Crossing> {
+    let wires: Vec<Polyline> = paths.iter().map(|p| wire(p)).collect();
+    polyline::crossings(&wires)
+        .into_iter()
+        .filter(|c| c.point != Point::ORIGIN)
+        .collect()
+}
+
+#[test]
+fn walks_runs() {
+    let w = wire("R8,U5,L5,D3");
+    assert_eq!(w.len(), 21);
+    assert_eq!(w.end(), Point::new(3, -2));
+    assert_eq!(w.segments().len(), 4);
+    assert_eq!(w.steps_to(Point::new(6, -5)), Some(15));
+    assert_eq!(w.steps_to(Point::ORIGIN), Some(0));
+    assert_eq!(w.steps_to(Point::new(1, 1)), None);
+}
+
+#[test]
+fn day3_examples() {
+    let found = crossings(&["R8,U5,L5,D3", "U7,R6,D4,L4"]);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].point, Point::new(3, -3));
+    assert_eq!(found[0].distance, 6);
+    assert_eq!(found.iter().map(|c| c.steps).min(), Some(30));
+
+    let examples = [
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72",
+            "U62,R66,U55,R34,D71,R55,D58,R83",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+            "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            135,
+            410,
+        ),
+    ];
+    for &(a, b, distance, steps) in &examples {
+        let found = crossings(&[a, b]);
+        assert_eq!(found[0].distance, distance);
+        assert_eq!(found.iter().map(|c| c.steps).min(), Some(steps));
+    }
+}
+
+#[test]
+fn first_visit_counts() {
+    // The first wire passes (2, 0) twice, the second time after 10 steps.
+    let found = crossings(&["R4,U2,L2,D4", "D2,R2,U4"]);
+    let at = found.iter().find(|c| c.point == Point::new(2, 0)).unwrap();
+    assert_eq!(at.paths, vec![(0, 2), (1, 6)]);
+    assert_eq!(at.steps, 8);
+}
+
+#[test]
+fn overlapping_wires() {
+    let found = crossings(&["R5", "U1,R2,D1,R4"]);
+    let points: Vec<Point> = found.iter().map(|c| c.point).collect();
+    assert_eq!(
+        points,
+        vec![
+            Point::new(2, 0),
+            Point::new(3, 0),
+            Point::new(4, 0),
+            Point::new(5, 0)
+        ]
+    );
+    assert_eq!(found[0].steps, 2 + 4);
+}
+
+#[test]
+fn more_than_two_wires() {
+    let found = crossings(&["R4", "U2,R2,D4", "D1,R2,U3,R1,D1"]);
+    assert_eq!(found[0].point, Point::new(2, 0));
+    assert_eq!(found[0].paths, vec![(0, 2), (1, 6), (2, 4)]);
+    assert_eq!(found[0].steps, 12);
+    // Wires only one other wire crosses still count.
+    assert!(found
+        .iter()
+        .any(|c| c.point == Point::new(2, -2) && c.paths.len() == 2));
+}
+
+#[test]
+fn diagonal_wires() {
+    let wires = vec![
+        Polyline::from_runs(Point::ORIGIN, vec![(Heading8::DownRight, 4)]),
+        Polyline::from_runs(
+            Point::ORIGIN,
+            vec![(Heading8::Right, 4), (Heading8::DownLeft, 4)],
+        ),
+    ];
+    let found = polyline::crossings(&wires);
+
+    // They meet at the start and halfway down.
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[1].point, Point::new(2, 2));
+    assert_eq!(found[1].distance, 4);
+    assert_eq!(found[1].paths, vec![(0, 2), (1, 6)]);
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+
+use geometry::segment::{self, Intersection};
+use geometry::{Point, Segment};
+
+fn segment(x0: i64, y0: i64, x1: i64, y1: i64) -> Segment {
+    Segment::new(Point::new(x0, y0), Point::new(x1, y1))
+}
+
+#[test]
+fn segments() {
+    let s = segment(3, 1, -1, 1);
+    assert!(s.is_horizontal());
+    assert_eq!(s.length(), 4);
+    assert!(s.contains(Point::new(0, 1)));
+    assert!(!s.contains(Point::new(0, 0)));
+    assert_eq!(
+        s.points().take(2).collect::<Vec<_>>(),
+        vec![Point::new(3, 1), Point::new(2, 1)]
+    );
+    assert!(!segment(0, 0, 0, 2).is_horizontal());
+}
+
+#[test]
+#[should_panic]
+fn crooked_segments() {
+    segment(0, 0, 1, 2);
+}
+
+#[test]
+fn diagonal_segments() {
+    let s = segment(0, 0, 3, -3);
+    assert!(s.is_diagonal());
+    assert_eq!(s.length(), 3);
+    assert!(s.contains(Point::new(2, -2)));
+    assert!(!s.contains(Point::new(2, -1)));
+
+    // Crossing between points doesn't count.
+    assert_eq!(s.meet(&segment(0, -1, 1, 0)), vec![]);
+    assert_eq!(s.meet(&segment(0, -2, 2, 0)), vec![Point::new(1, -1)]);
+    assert_eq!(s.meet(&segment(2, 5, 2, -5)), vec![Point::new(2, -2)]);
+    assert_eq!(
+        s.meet(&segment(4, -4, 2, -2)),
+        vec![Point::new(2, -2), Point::new(3, -3)]
+    );
+    assert_eq!(s.meet(&segment(1, 0, 4, -3)), vec![]);
+}
+
+#[test]
+fn crossing_and_touching() {
+    let segments = [
+        segment(0, 0, 10, 0),
+        segment(5, -5, 5, 5),
+        segment(10, 0, 10, 3),
+        segment(20, 20, 20, 30),
+    ];
+    let found: BTreeSet<Intersection> = segment::intersections(&segments).into_iter().collect();
+
+    let expected: BTreeSet<Intersection> = vec![
+        Intersection {
+            first: 0,
+            second: 1,
+            point: Point::new(5, 0),
+        },
+        Intersection {
+            first: 0,
+            second: 2,
+            point: Point::new(10, 0),
+        },
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn overlaps_meet_all_along() {
+    let segments = [
+        segment(0, 0, 4, 0),
+        segment(6, 0, 2, 0),
+        segment(3, 1, 3, 5),
+        segment(3, 4, 3, 8),
+    ];
+    let points: Vec<Point> = segment::intersections(&segments)
+        .iter()
+        .map(|i| i.point)
+        .collect();
+
+    assert_eq!(points.len(), 3 + 2);
+    assert!(points.contains(&Point::new(2, 0)));
+    assert!(points.contains(&Point::new(4, 0)));
+    assert!(points.contains(&Point::new(3, 5)));
+}
+
+/// Every pair, point by point, to check the sweep against.
+fn brute_force(segments: &[Segment]) -> BTreeSet<Intersection> {
+    let mut found = BTreeSet::new();
+    for (i, a) in segments.iter().enumerate() {
+        for (j, b) in segments.iter().enumerate().skip(i + 1) {
+            for point in a.points().filter(|&p| b.contains(p)) {
+                found.insert(Intersection {
+                    first: i,
+                    second: j,
+                    point,
+                });
+            }
+        }
+    }
+    found
+}
+
+#[test]
+fn matches_brute_force() {
+    let mut seed: u64 = 2019;
+    let mut next = |n: i64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) as i64 % n
+    };
+
+    for _ in 0..20 {
+        let segments: Vec<Segment> = (0..40)
+            .map(|_| {
+                let (x, y, len) = (next(20), next(20), next(8));
+                match next(4) {
+                    0 => segment(x, y, x + len, y),
+                    1 => segment(x, y, x, y - len),
+                    2 => segment(x, y, x + len, y + len),
+                    _ => segment(x, y, x - len, y + len),
+                }
+            })
+            .collect();
+
+        let found: Vec<Intersection> = segment::intersections(&segments);
+        let unique: BTreeSet<Intersection> = found.iter().cloned().collect();
+        assert_eq!(found.len(), unique.len());
+        assert_eq!(unique, brute_force(&segments));
+    }
+}
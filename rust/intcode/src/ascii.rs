@@ -0,0 +1,95 @@
+//! Line-oriented text I/O for the puzzles that speak ASCII.
+//!
+//! Each line of input goes in as its character codes followed by a newline.
+//! Output below 128 is printed as text; anything else, usually the answer
+//! at the very end, is printed as a number on its own line.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::prelude::*;
+use std::io::{self, BufReader, Stdin, Stdout};
+use std::path::Path;
+
+use crate::{Cell, Device};
+
+pub struct Ascii<R, W> {
+    input: R,
+    output: W,
+    script: VecDeque<String>,
+    pending: VecDeque<Cell>,
+}
+
+impl Ascii<BufReader<Stdin>, Stdout> {
+    pub fn new() -> Self {
+        Ascii::with(BufReader::new(io::stdin()), io::stdout())
+    }
+}
+
+impl Default for Ascii<BufReader<Stdin>, Stdout> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: BufRead, W: Write> Ascii<R, W> {
+    pub fn with(input: R, output: W) -> Self {
+        Ascii {
+            input,
+            output,
+            script: VecDeque::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Queues commands to send before anything is read from the input.
+    /// Each one is echoed as it's sent so the transcript reads as if it
+    /// had been typed.
+    pub fn script(&mut self, commands: &str) {
+        self.script
+            .extend(commands.lines().map(|l| l.trim_end().to_string()));
+    }
+
+    pub fn script_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.script(&fs::read_to_string(path)?);
+        Ok(())
+    }
+
+    pub fn output(&self) -> &W {
+        &self.output
+    }
+
+    fn next_line(&mut self) -> String {
+        self.output.flush().unwrap();
+
+        if let Some(line) = self.script.pop_front() {
+            writeln!(self.output, "{}", line).unwrap();
+            return line;
+        }
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line).unwrap() == 0 {
+            panic!("input exhausted");
+        }
+        line.trim_end_matches(&['\r', '\n'][..]).to_string()
+    }
+}
+
+impl<R: BufRead, W: Write> Device for Ascii<R, W> {
+    fn read(&mut self) -> Cell {
+        if self.pending.is_empty() {
+            let line = self.next_line();
+            self.pending.extend(line.bytes().map(Cell::from));
+            self.pending.push_back(10);
+        }
+
+        self.pending.pop_front().unwrap()
+    }
+
+    fn write(&mut self, value: Cell) {
+        if (0..128).contains(&value) {
+            write!(self.output, "{}", value as u8 as char).unwrap();
+        } else {
+            writeln!(self.output, "{}", value).unwrap();
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::process;
+
+use intcode::inspect::{self, Change};
+use intcode::patch::{self, Patch, Search};
+use intcode::{Cell, Device, Intcode};
+
+const GRID_WIDTH: usize = 64;
+
+fn usage() -> ! {
+    eprintln!("usage: memview diff BEFORE AFTER [--grid]");
+    eprintln!("       memview inputs PROGRAM [OPTIONS] [--at N] [--grid]");
+    eprintln!("       memview heat PROGRAM [OPTIONS] [--grid]");
+    eprintln!("       memview find PROGRAM [OPTIONS] VALUE[@INPUT]...");
+    eprintln!();
+    eprintln!("options: --input N,...  joystick input, zeroes once it runs out");
+    eprintln!("         --patch FILE   patches to apply to the program");
+    process::exit(2);
+}
+
+/// Answers input from a list, then with zeroes once the list runs out,
+/// which holds a joystick in the middle. Output is thrown away.
+struct Scripted(VecDeque<Cell>);
+
+impl Device for Scripted {
+    fn read(&mut self) -> Cell {
+        self.0.pop_front().unwrap_or(0)
+    }
+
+    fn write(&mut self, _value: Cell) {}
+}
+
+struct Options {
+    paths: Vec<String>,
+    input: Vec<Cell>,
+    patches: Vec<Patch>,
+    at: Option<usize>,
+    grid: bool,
+}
+
+fn options(args: impl Iterator<Item = String>) -> Options {
+    let mut options = Options {
+        paths: Vec::new(),
+        input: Vec::new(),
+        patches: Vec::new(),
+        at: None,
+        grid: false,
+    };
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--grid" => options.grid = true,
+            "--input" => options.input = intcode::parse(&args.next().unwrap_or_else(|| usage())),
+            "--patch" => {
+                let path = args.next().unwrap_or_else(|| usage());
+                options
+                    .patches
+                    .extend(patch::load(&path).unwrap_or_else(|e| {
+                        eprintln!("{}: {}", path, e);
+                        process::exit(1);
+                    }));
+            }
+            "--at" => {
+                let at = args.next().unwrap_or_else(|| usage());
+                options.at = Some(at.parse().unwrap_or_else(|_| usage()));
+            }
+            _ if !arg.starts_with("--") => options.paths.push(arg),
+            _ => usage(),
+        }
+    }
+
+    options
+}
+
+fn load(path: &str) -> Vec<Cell> {
+    match fs::read_to_string(path) {
+        Ok(data) => intcode::parse(&data),
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn machine(options: &Options) -> Intcode<Scripted> {
+    let memory = load(&options.paths[0]);
+    let mut cpu = Intcode::new(&memory, Scripted(options.input.iter().cloned().collect()));
+    cpu.patch(&options.patches);
+    cpu
+}
+
+fn show_changes(changes: &[Change], grid: bool) {
+    if grid {
+        print!(
+            "{}",
+            inspect::grid(&inspect::changed_cells(changes), GRID_WIDTH)
+        );
+    } else {
+        print!("{}", inspect::changes_table(changes));
+    }
+}
+
+fn diff(options: &Options) {
+    if options.paths.len() != 2 {
+        usage();
+    }
+
+    let before = load(&options.paths[0]);
+    let after = load(&options.paths[1]);
+    show_changes(&inspect::diff(&before, &after), options.grid);
+}
+
+/// Shows what changed between one input being read and the next.
+fn inputs(options: &Options) {
+    if options.paths.len() != 1 {
+        usage();
+    }
+
+    let mut cpu = machine(options);
+    let mut snapshots: Vec<(u64, Vec<Cell>)> = Vec::new();
+
+    loop {
+        if cpu.opcode() == 3 {
+            snapshots.push((cpu.steps(), cpu.memory().to_vec()));
+        }
+
+        if !cpu.step() {
+            break;
+        }
+    }
+    snapshots.push((cpu.steps(), cpu.memory().to_vec()));
+
+    for (n, pair) in snapshots.windows(2).enumerate() {
+        if matches!(options.at, Some(at) if at != n) {
+            continue;
+        }
+
+        let changes = inspect::diff(&pair[0].1, &pair[1].1);
+        println!(
+            "input {} (steps {} to {}): {} cells changed",
+            n,
+            pair[0].0,
+            pair[1].0,
+            changes.len()
+        );
+        show_changes(&changes, options.grid);
+    }
+}
+
+fn heat(options: &Options) {
+    if options.paths.len() != 1 {
+        usage();
+    }
+
+    let mut cpu = machine(options);
+    cpu.count_writes();
+    cpu.run();
+
+    let writes = cpu.writes().unwrap();
+    if options.grid {
+        print!("{}", inspect::grid(writes, GRID_WIDTH));
+    } else {
+        print!("{}", inspect::writes_table(writes));
+    }
+}
+
+/// Finds the cells holding each VALUE just before input INPUT is read,
+/// or at the start when there's no INPUT.
+fn find(options: &Options) {
+    if options.paths.len() < 2 {
+        usage();
+    }
+
+    let mut wanted: Vec<(Option<u64>, Cell)> = options.paths[1..]
+        .iter()
+        .map(|spec| {
+            let mut fields = spec.splitn(2, '@');
+            let value = fields.next().unwrap().parse().unwrap_or_else(|_| usage());
+            let at = fields.next().map(|n| n.parse().unwrap_or_else(|_| usage()));
+            (at, value)
+        })
+        .collect();
+    wanted.sort();
+
+    let mut cpu = machine(options);
+    let mut search: Option<Search> = None;
+
+    for (at, value) in wanted {
+        if let Some(n) = at {
+            while cpu.inputs() < n || cpu.opcode() != 3 {
+                if !cpu.step() {
+                    eprintln!("program halted before input {}", n);
+                    process::exit(1);
+                }
+            }
+        }
+
+        match &mut search {
+            Some(search) => search.refine(cpu.memory(), value),
+            None => search = Some(Search::new(cpu.memory(), value)),
+        }
+    }
+
+    for addr in search.unwrap().candidates() {
+        println!("{}", addr);
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().unwrap_or_else(|| usage());
+    let options = options(args);
+
+    match command.as_str() {
+        "diff" => diff(&options),
+        "inputs" => inputs(&options),
+        "heat" => heat(&options),
+        "find" => find(&options),
+        _ => usage(),
+    }
+}
@@ -0,0 +1,385 @@
+//! Reference programs for checking an Intcode interpreter.
+//!
+//! The hand-written cases live in `conformance.txt` next to this crate's
+//! manifest; the exhaustive opcode and addressing mode combinations are
+//! generated here. Interpreters written before some opcode existed only
+//! claim the features they support and skip the rest.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use crate::Cell;
+
+const SUITE: &str = include_str!("../conformance.txt");
+
+/// Base used by generated cases that exercise relative mode. It's bigger
+/// than the generated programs so relative offsets come out negative.
+const RELATIVE_BASE: Cell = 20;
+
+type BinaryOp = fn(Cell, Cell) -> Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    /// Immediate mode parameters (day 5).
+    Modes,
+    /// Opcodes 3 and 4 (day 5).
+    Io,
+    /// Opcodes 5 to 8 (day 5, part 2).
+    Jumps,
+    /// Opcode 9 and relative mode (day 9).
+    Relative,
+    /// Memory past the end of the program (day 9).
+    Growth,
+    /// Values that don't fit in 32 bits (day 9).
+    Wide,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 6] = [
+        Feature::Modes,
+        Feature::Io,
+        Feature::Jumps,
+        Feature::Relative,
+        Feature::Growth,
+        Feature::Wide,
+    ];
+
+    fn from_name(name: &str) -> Self {
+        match name {
+            "modes" => Feature::Modes,
+            "io" => Feature::Io,
+            "jumps" => Feature::Jumps,
+            "relative" => Feature::Relative,
+            "growth" => Feature::Growth,
+            "wide" => Feature::Wide,
+            x => panic!("unknown feature: {}", x),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Case {
+    pub name: String,
+    pub program: Vec<Cell>,
+    pub input: Vec<Cell>,
+    pub output: Vec<Cell>,
+    /// Expected start of memory once the program halts.
+    pub memory: Option<Vec<Cell>>,
+    pub requires: Vec<Feature>,
+}
+
+impl Case {
+    pub fn supported_by(&self, features: &[Feature]) -> bool {
+        self.requires.iter().all(|f| features.contains(f))
+    }
+
+    fn check(&self, output: &[Cell], memory: &[Cell]) -> Result<(), String> {
+        if output != &self.output[..] {
+            return Err(format!(
+                "expected output {:?}, got {:?}",
+                self.output, output
+            ));
+        }
+
+        if let Some(expected) = &self.memory {
+            if memory.len() < expected.len() || memory[..expected.len()] != expected[..] {
+                return Err(format!("expected memory {:?}, got {:?}", expected, memory));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs every case allowed by `features` through `interpreter`, which is
+/// handed a case and returns the output it produced and its final memory.
+/// Panics listing every case that failed.
+pub fn check<F>(features: &[Feature], mut interpreter: F)
+where
+    F: FnMut(&Case) -> (Vec<Cell>, Vec<Cell>),
+{
+    let mut ran = 0;
+    let mut failures = Vec::new();
+
+    for case in suite().iter().filter(|c| c.supported_by(features)) {
+        ran += 1;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| interpreter(case)));
+        let result = match result {
+            Ok((output, memory)) => case.check(&output, &memory),
+            Err(_) => Err("interpreter panicked".to_string()),
+        };
+
+        if let Err(message) = result {
+            failures.push(format!("{}: {}", case.name, message));
+        }
+    }
+
+    assert!(ran > 0, "no conformance cases for {:?}", features);
+    assert!(
+        failures.is_empty(),
+        "{} of {} conformance cases failed:\n{}",
+        failures.len(),
+        ran,
+        failures.join("\n")
+    );
+}
+
+/// Every case in the suite, hand-written ones first.
+pub fn suite() -> Vec<Case> {
+    let mut cases = parse(SUITE);
+    cases.extend(arithmetic_cases());
+    cases.extend(jump_cases());
+    cases.extend(io_cases());
+    cases.extend(relative_base_cases());
+    cases
+}
+
+fn values(line: &str) -> Vec<Cell> {
+    if line.is_empty() {
+        return Vec::new();
+    }
+
+    line.split(',').map(|e| e.trim().parse().unwrap()).collect()
+}
+
+fn program(value: &str) -> Vec<Cell> {
+    if let Some(path) = value.strip_prefix('@') {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(path);
+        let data = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("can't read {}: {}", path.display(), e));
+        values(data.trim())
+    } else {
+        values(value)
+    }
+}
+
+fn parse(data: &str) -> Vec<Case> {
+    let mut cases: Vec<Case> = Vec::new();
+
+    for line in data.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            cases.push(Case {
+                name: line[1..line.len() - 1].to_string(),
+                ..Case::default()
+            });
+            continue;
+        }
+
+        let case = cases.last_mut().expect("case field before first header");
+        let (key, value) = match line.find('=') {
+            Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+            None => panic!("{}: malformed line: {}", case.name, line),
+        };
+
+        match key {
+            "program" => case.program = program(value),
+            "input" => case.input = values(value),
+            "output" => case.output = values(value),
+            "memory" => case.memory = Some(values(value)),
+            "requires" => {
+                case.requires = value
+                    .split(',')
+                    .map(|f| Feature::from_name(f.trim()))
+                    .collect()
+            }
+            x => panic!("{}: unknown key: {}", case.name, x),
+        }
+    }
+
+    cases
+}
+
+/// Encodes the parameter for a value stored at `addr` under `mode`.
+fn param(mode: Cell, addr: Cell, value: Cell) -> Cell {
+    match mode {
+        0 => addr,
+        1 => value,
+        2 => addr - RELATIVE_BASE,
+        x => panic!("unknown addressing mode: {}", x),
+    }
+}
+
+/// Prefix that sets up the relative base, if any mode needs it, along with
+/// the features the modes need.
+fn prologue(modes: &[Cell]) -> (Vec<Cell>, Vec<Feature>) {
+    let mut requires = Vec::new();
+
+    if modes.contains(&1) {
+        requires.push(Feature::Modes);
+    }
+
+    if modes.contains(&2) {
+        requires.push(Feature::Modes);
+        requires.push(Feature::Relative);
+        (vec![109, RELATIVE_BASE], requires)
+    } else {
+        (Vec::new(), requires)
+    }
+}
+
+/// Opcodes 1, 2, 7 and 8 under every mode combination.
+fn arithmetic_cases() -> Vec<Case> {
+    let ops: [(Cell, &str, BinaryOp); 4] = [
+        (1, "add", |a, b| a + b),
+        (2, "mul", |a, b| a * b),
+        (7, "lt", |a, b| if a < b { 1 } else { 0 }),
+        (8, "eq", |a, b| if a == b { 1 } else { 0 }),
+    ];
+    let mut cases = Vec::new();
+
+    for &(opcode, name, op) in ops.iter() {
+        for &(a, b) in [(5, 7), (7, 5), (7, 7)].iter() {
+            for m1 in 0..3 {
+                for m2 in 0..3 {
+                    for &m3 in [0, 2].iter() {
+                        let (mut program, mut requires) = prologue(&[m1, m2, m3]);
+                        let base = program.len() as Cell;
+                        let (addr_a, addr_b, dest) = (base + 7, base + 8, base + 9);
+
+                        program.extend(&[
+                            opcode + m1 * 100 + m2 * 1000 + m3 * 10000,
+                            param(m1, addr_a, a),
+                            param(m2, addr_b, b),
+                            param(m3, dest, 0),
+                            4,
+                            dest,
+                            99,
+                            a,
+                            b,
+                            0,
+                        ]);
+
+                        requires.push(Feature::Io);
+                        if opcode > 2 {
+                            requires.push(Feature::Jumps);
+                        }
+
+                        cases.push(Case {
+                            name: format!("{} modes {}{}{} ({}, {})", name, m3, m2, m1, a, b),
+                            program,
+                            output: vec![op(a, b)],
+                            requires,
+                            ..Case::default()
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    cases
+}
+
+/// Opcodes 5 and 6 under every mode combination, taken and not taken.
+fn jump_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    for &(opcode, name) in [(5, "jump-if-true"), (6, "jump-if-false")].iter() {
+        for &condition in [0, 3].iter() {
+            for m1 in 0..3 {
+                for m2 in 0..3 {
+                    let (mut program, mut requires) = prologue(&[m1, m2]);
+                    let base = program.len() as Cell;
+                    let target = base + 6;
+                    let (addr_condition, addr_target) = (base + 9, base + 10);
+
+                    program.extend(&[
+                        opcode + m1 * 100 + m2 * 1000,
+                        param(m1, addr_condition, condition),
+                        param(m2, addr_target, target),
+                        104,
+                        0,
+                        99,
+                        104,
+                        1,
+                        99,
+                        condition,
+                        target,
+                    ]);
+
+                    requires.extend(&[Feature::Modes, Feature::Io, Feature::Jumps]);
+                    let taken = (opcode == 5) == (condition != 0);
+
+                    cases.push(Case {
+                        name: format!("{} modes {}{} ({})", name, m2, m1, condition),
+                        program,
+                        output: vec![if taken { 1 } else { 0 }],
+                        requires,
+                        ..Case::default()
+                    });
+                }
+            }
+        }
+    }
+
+    cases
+}
+
+/// Opcodes 3 and 4 under every mode they accept.
+fn io_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    for &mode in [0, 2].iter() {
+        let (mut program, mut requires) = prologue(&[mode]);
+        let dest = program.len() as Cell + 5;
+        program.extend(&[3 + mode * 100, param(mode, dest, 0), 4, dest, 99, 0]);
+        requires.push(Feature::Io);
+
+        cases.push(Case {
+            name: format!("input mode {}", mode),
+            program,
+            input: vec![42],
+            output: vec![42],
+            requires,
+            ..Case::default()
+        });
+    }
+
+    for mode in 0..3 {
+        let (mut program, mut requires) = prologue(&[mode]);
+        let addr = program.len() as Cell + 3;
+        program.extend(&[4 + mode * 100, param(mode, addr, -17), 99, -17]);
+        requires.push(Feature::Io);
+
+        cases.push(Case {
+            name: format!("output mode {}", mode),
+            program,
+            output: vec![-17],
+            requires,
+            ..Case::default()
+        });
+    }
+
+    cases
+}
+
+/// Opcode 9 under every mode, moving the base past the value it then reads.
+fn relative_base_cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    for mode in 0..3 {
+        // The base starts at 10 and moves by 5, leaving the sentinel at
+        // address 8 a negative offset away.
+        let adjust = match mode {
+            0 => 7,
+            1 => 5,
+            _ => 7 - 10,
+        };
+
+        cases.push(Case {
+            name: format!("adjust relative base mode {}", mode),
+            program: vec![109, 10, 9 + mode * 100, adjust, 204, -7, 99, 5, 1234],
+            output: vec![1234],
+            requires: vec![Feature::Modes, Feature::Io, Feature::Relative],
+            ..Case::default()
+        });
+    }
+
+    cases
+}
@@ -0,0 +1,113 @@
+//! Looking at what a program does to its memory: which cells changed
+//! between two snapshots and how often each address gets written.
+
+use std::fmt::Write;
+
+use crate::Cell;
+
+/// Colours for the heat grid, coolest first.
+const RAMP: [u8; 12] = [22, 28, 34, 40, 46, 118, 190, 226, 220, 214, 208, 196];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub addr: usize,
+    pub before: Cell,
+    pub after: Cell,
+}
+
+/// Cells that differ between two snapshots. Memory past the end of the
+/// shorter one counts as zero, the same as the machine sees it.
+pub fn diff(before: &[Cell], after: &[Cell]) -> Vec<Change> {
+    let len = before.len().max(after.len());
+
+    (0..len)
+        .map(|addr| Change {
+            addr,
+            before: before.get(addr).cloned().unwrap_or(0),
+            after: after.get(addr).cloned().unwrap_or(0),
+        })
+        .filter(|c| c.before != c.after)
+        .collect()
+}
+
+pub fn changes_table(changes: &[Change]) -> String {
+    let mut table = format!("{:>8} {:>16} {:>16}\n", "addr", "before", "after");
+
+    for change in changes {
+        writeln!(
+            table,
+            "{:>8} {:>16} {:>16}",
+            change.addr, change.before, change.after
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+/// Every address written at least once, busiest first, with a bar scaled
+/// to the busiest.
+pub fn writes_table(writes: &[u64]) -> String {
+    let max = writes.iter().cloned().max().unwrap_or(0);
+    let mut busy: Vec<(usize, u64)> = writes
+        .iter()
+        .cloned()
+        .enumerate()
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    busy.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut table = format!("{:>8} {:>10}\n", "addr", "writes");
+
+    for (addr, count) in busy {
+        let bar = (count * 40).div_ceil(max);
+        writeln!(
+            table,
+            "{:>8} {:>10} {}",
+            addr,
+            count,
+            "#".repeat(bar as usize)
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+/// Renders one cell per address, `width` to a row, coloured on a log
+/// scale from the quietest to the busiest. Zero is left blank.
+pub fn grid(values: &[u64], width: usize) -> String {
+    let max = values.iter().cloned().max().unwrap_or(0);
+    let scale = ((max + 1) as f64).ln();
+    let mut grid = String::new();
+
+    for (row, chunk) in values.chunks(width).enumerate() {
+        write!(grid, "{:>8} ", row * width).unwrap();
+
+        for &value in chunk {
+            if value == 0 {
+                grid.push_str("  ");
+            } else {
+                let level = ((value + 1) as f64).ln() / scale * (RAMP.len() - 1) as f64;
+                let colour = RAMP[level.round() as usize];
+                write!(grid, "\x1b[38;5;{}m\u{2588}\u{2588}\x1b[0m", colour).unwrap();
+            }
+        }
+
+        grid.push('\n');
+    }
+
+    grid
+}
+
+/// Marks each address that changed, for showing a diff with `grid`.
+pub fn changed_cells(changes: &[Change]) -> Vec<u64> {
+    let len = changes.last().map(|c| c.addr + 1).unwrap_or(0);
+    let mut cells = vec![0; len];
+
+    for change in changes {
+        cells[change.addr] = 1;
+    }
+
+    cells
+}
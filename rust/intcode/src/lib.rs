@@ -0,0 +1,378 @@
+use std::collections::VecDeque;
+use std::io;
+use std::io::prelude::*;
+
+mod ascii;
+pub mod conformance;
+pub mod inspect;
+pub mod patch;
+pub mod robot;
+pub mod screen;
+pub mod session;
+
+pub use crate::ascii::Ascii;
+use crate::patch::{Patch, When};
+use crate::session::{Event, Session};
+
+pub type Addr = u32;
+pub type Cell = i64;
+
+/// Whatever sits on the other end of opcodes 3 and 4.
+pub trait Device {
+    fn read(&mut self) -> Cell;
+    fn write(&mut self, value: Cell);
+}
+
+/// Prompts on stdin for input and prints output, one value per line.
+pub struct Console;
+
+impl Device for Console {
+    fn read(&mut self) -> Cell {
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+
+        print!("> ");
+        stdout.flush().unwrap();
+        let mut value = String::new();
+        stdin.read_line(&mut value).unwrap();
+        value.trim().parse().unwrap()
+    }
+
+    fn write(&mut self, value: Cell) {
+        println!("# {}", value);
+    }
+}
+
+/// Feeds input from a queue and collects output.
+#[derive(Default)]
+pub struct Buffer {
+    pub input: VecDeque<Cell>,
+    pub output: Vec<Cell>,
+}
+
+impl Buffer {
+    pub fn new(input: &[Cell]) -> Self {
+        Buffer {
+            input: input.iter().cloned().collect(),
+            output: Vec::new(),
+        }
+    }
+}
+
+impl Device for Buffer {
+    fn read(&mut self) -> Cell {
+        self.input.pop_front().expect("input exhausted")
+    }
+
+    fn write(&mut self, value: Cell) {
+        self.output.push(value);
+    }
+}
+
+pub struct Intcode<D> {
+    memory: Vec<Cell>,
+    pc: Addr,
+    relative_base: Cell,
+    steps: u64,
+    inputs: u64,
+    session: Option<Session>,
+    writes: Option<Vec<u64>>,
+    patches: Vec<Patch>,
+    pub device: D,
+}
+
+/// A copy of the machine as it stands, for trying things out from here.
+/// The copy isn't recording or replaying.
+impl<D: Clone> Clone for Intcode<D> {
+    fn clone(&self) -> Self {
+        Intcode {
+            memory: self.memory.clone(),
+            pc: self.pc,
+            relative_base: self.relative_base,
+            steps: self.steps,
+            inputs: self.inputs,
+            session: None,
+            writes: self.writes.clone(),
+            patches: self.patches.clone(),
+            device: self.device.clone(),
+        }
+    }
+}
+
+impl<D: Device> Intcode<D> {
+    pub fn new(contents: &[Cell], device: D) -> Self {
+        Intcode {
+            memory: contents.to_vec(),
+            pc: 0,
+            relative_base: 0,
+            steps: 0,
+            inputs: 0,
+            session: None,
+            writes: None,
+            patches: Vec::new(),
+            device,
+        }
+    }
+
+    pub fn memory(&self) -> &[Cell] {
+        &self.memory
+    }
+
+    pub fn pc(&self) -> Addr {
+        self.pc
+    }
+
+    /// Opcode of the instruction about to be executed.
+    pub fn opcode(&self) -> u32 {
+        (self.memory[self.pc as usize] % 100) as u32
+    }
+
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Number of inputs read so far.
+    pub fn inputs(&self) -> u64 {
+        self.inputs
+    }
+
+    /// Sets a cell, growing memory if need be.
+    pub fn poke(&mut self, addr: usize, value: Cell) {
+        if self.memory.len() <= addr {
+            self.memory.resize(addr + 1, 0);
+        }
+        self.memory[addr] = value;
+    }
+
+    /// Applies load-time patches now and holds on to the rest until
+    /// they're due.
+    pub fn patch(&mut self, patches: &[Patch]) {
+        for patch in patches {
+            if patch.when == When::Load {
+                self.poke(patch.addr, patch.value);
+            } else {
+                self.patches.push(*patch);
+            }
+        }
+    }
+
+    fn apply_patches(&mut self) {
+        let (steps, inputs) = (self.steps, self.inputs);
+        let reading = self.opcode() == 3;
+        let mut due = Vec::new();
+
+        self.patches.retain(|patch| {
+            let (now, keep) = match patch.when {
+                When::Load => (true, false),
+                When::Step(n) => (steps >= n, steps < n),
+                When::Input(n) => {
+                    let now = inputs > n || (inputs == n && reading);
+                    (now, !now)
+                }
+                When::Always => (true, true),
+            };
+
+            if now {
+                due.push((patch.addr, patch.value));
+            }
+            keep
+        });
+
+        for (addr, value) in due {
+            self.poke(addr, value);
+        }
+    }
+
+    /// Starts counting writes to each address.
+    pub fn count_writes(&mut self) {
+        self.writes = Some(vec![0; self.memory.len()]);
+    }
+
+    /// Writes to each address since `count_writes` was called.
+    pub fn writes(&self) -> Option<&[u64]> {
+        self.writes.as_deref()
+    }
+
+    /// Writes every input and output to `output` as the program runs.
+    pub fn record(&mut self, output: Box<dyn Write>) {
+        self.session = Some(Session::Record(output));
+    }
+
+    /// Feeds recorded input back in place of the device's and checks the
+    /// program's output against the recording, panicking at the first
+    /// difference. Output still goes to the device.
+    pub fn replay(&mut self, events: Vec<Event>) {
+        self.session = Some(Session::Replay(events.into_iter().collect()));
+    }
+
+    fn get_op(&self) -> (u32, Modes) {
+        let mut op = self.memory[self.pc as usize];
+        let mut modes = Vec::new();
+
+        let opcode = op % 100;
+        op /= 100;
+
+        while op != 0 {
+            modes.push((op % 10) as u32);
+            op /= 10;
+        }
+
+        (opcode as u32, Modes::new(modes))
+    }
+
+    fn addr(&mut self, addr: Addr, mode: u32) -> usize {
+        let addr = match mode {
+            0 => self.memory[addr as usize] as usize,
+            1 => addr as usize,
+            2 => (self.memory[addr as usize] + self.relative_base) as usize,
+            x => panic!("unknown addressing mode: {}", x),
+        };
+
+        if self.memory.len() <= addr {
+            self.memory.resize(addr + 1, 0);
+        }
+
+        addr
+    }
+
+    fn get(&mut self, addr: Addr, mode: u32) -> Cell {
+        let addr = self.addr(addr, mode);
+        self.memory[addr]
+    }
+
+    fn set(&mut self, addr: Addr, mode: u32, value: Cell) {
+        let addr = self.addr(addr, mode);
+        self.memory[addr] = value;
+
+        if let Some(writes) = &mut self.writes {
+            if writes.len() <= addr {
+                writes.resize(addr + 1, 0);
+            }
+            writes[addr] += 1;
+        }
+    }
+
+    fn op_3<F>(&mut self, mut modes: Modes, op: F)
+    where
+        F: Fn(Cell, Cell) -> Cell,
+    {
+        modes.ensure(3);
+
+        let arg1 = self.get(self.pc + 1, modes.0[0]);
+        let arg2 = self.get(self.pc + 2, modes.0[1]);
+        self.set(self.pc + 3, modes.0[2], op(arg1, arg2));
+        self.pc += 4;
+    }
+
+    fn conditional_jump<F>(&mut self, mut modes: Modes, op: F)
+    where
+        F: Fn(Cell) -> bool,
+    {
+        modes.ensure(2);
+        if op(self.get(self.pc + 1, modes.0[0])) {
+            self.pc = self.get(self.pc + 2, modes.0[1]) as Addr;
+        } else {
+            self.pc += 3;
+        }
+    }
+
+    fn read_stdin(&mut self, mut modes: Modes) {
+        modes.ensure(1);
+        self.inputs += 1;
+        let step = self.steps;
+        let value = match self.session.as_mut().and_then(|s| s.input(step)) {
+            Some(value) => value,
+            None => self.device.read(),
+        };
+        if let Some(session) = &mut self.session {
+            session.log_input(step, value);
+        }
+        self.set(self.pc + 1, modes.0[0], value);
+        self.pc += 2;
+    }
+
+    fn write_stdout(&mut self, mut modes: Modes) {
+        modes.ensure(1);
+        let value = self.get(self.pc + 1, modes.0[0]);
+        if let Some(session) = &mut self.session {
+            session.output(self.steps, value);
+        }
+        self.device.write(value);
+        self.pc += 2;
+    }
+
+    fn set_relative_base(&mut self, mut modes: Modes) {
+        modes.ensure(1);
+        self.relative_base += self.get(self.pc + 1, modes.0[0]);
+        self.pc += 2;
+    }
+
+    /// Executes one instruction, returning false once the program halts.
+    pub fn step(&mut self) -> bool {
+        if !self.patches.is_empty() {
+            self.apply_patches();
+        }
+
+        let (opcode, modes) = self.get_op();
+        match opcode {
+            1 => self.op_3(modes, |a, b| a + b),
+            2 => self.op_3(modes, |a, b| a * b),
+            3 => self.read_stdin(modes),
+            4 => self.write_stdout(modes),
+            5 => self.conditional_jump(modes, |v| v != 0),
+            6 => self.conditional_jump(modes, |v| v == 0),
+            7 => self.op_3(modes, |a, b| if a < b { 1 } else { 0 }),
+            8 => self.op_3(modes, |a, b| if a == b { 1 } else { 0 }),
+            9 => self.set_relative_base(modes),
+            99 => {
+                if let Some(session) = &mut self.session {
+                    session.finish(self.steps);
+                }
+                return false;
+            }
+            x => panic!("unknown opcode: {}", x),
+        };
+
+        self.steps += 1;
+        true
+    }
+
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+
+    pub fn dump(&self) {
+        self.dump_to(io::stdout()).unwrap();
+    }
+
+    /// Writes memory in the same comma-separated form programs are
+    /// loaded from, so a dump can be loaded or compared later.
+    pub fn dump_to<W: Write>(&self, mut output: W) -> io::Result<()> {
+        let contents: Vec<String> = self.memory.iter().map(|e| e.to_string()).collect();
+        writeln!(output, "{}", contents.join(","))
+    }
+}
+
+#[derive(Debug)]
+struct Modes(pub Vec<u32>);
+
+impl Modes {
+    pub fn new(values: Vec<u32>) -> Self {
+        Modes(values)
+    }
+
+    pub fn ensure(&mut self, size: usize) {
+        while self.0.len() < size {
+            self.0.push(0);
+        }
+    }
+}
+
+/// Parses a comma-separated program as found in the puzzle inputs.
+pub fn parse(data: &str) -> Vec<Cell> {
+    data.trim()
+        .split(',')
+        .map(|e| e.trim().parse().unwrap())
+        .collect()
+}
@@ -0,0 +1,83 @@
+use std::fs::{self, File};
+use std::io;
+use std::process;
+
+use intcode::patch::{self, Patch};
+use intcode::{session, Ascii, Cell, Console, Device, Intcode};
+
+fn usage() -> ! {
+    eprintln!("usage: intcode [--ascii] [--script FILE] [--record FILE | --replay FILE] [--dump FILE]\n               [--patch FILE]... PROGRAM");
+    process::exit(2);
+}
+
+fn run<D: Device>(
+    memory: &[Cell],
+    device: D,
+    patches: &[Patch],
+    record: Option<String>,
+    replay: Option<String>,
+    dump: Option<String>,
+) -> io::Result<()> {
+    let mut cpu = Intcode::new(memory, device);
+    cpu.patch(patches);
+
+    if let Some(path) = record {
+        cpu.record(session::create(path)?);
+    }
+
+    if let Some(path) = replay {
+        cpu.replay(session::load(path)?);
+    }
+
+    cpu.run();
+
+    if let Some(path) = dump {
+        cpu.dump_to(File::create(path)?)?;
+    }
+
+    Ok(())
+}
+
+fn main() -> std::io::Result<()> {
+    let mut ascii = false;
+    let mut script = None;
+    let mut record = None;
+    let mut replay = None;
+    let mut dump = None;
+    let mut patches = Vec::new();
+    let mut prog = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ascii" => ascii = true,
+            "--script" => {
+                ascii = true;
+                script = Some(args.next().unwrap_or_else(|| usage()));
+            }
+            // Both would end up in the same session, and the replay
+            // would win.
+            "--record" | "--replay" if record.is_some() || replay.is_some() => usage(),
+            "--record" => record = Some(args.next().unwrap_or_else(|| usage())),
+            "--replay" => replay = Some(args.next().unwrap_or_else(|| usage())),
+            "--dump" => dump = Some(args.next().unwrap_or_else(|| usage())),
+            "--patch" => patches.extend(patch::load(args.next().unwrap_or_else(|| usage()))?),
+            _ if prog.is_none() && !arg.starts_with("--") => prog = Some(arg),
+            _ => usage(),
+        }
+    }
+
+    let prog = prog.unwrap_or_else(|| usage());
+    let memory = intcode::parse(&fs::read_to_string(prog)?);
+
+    if ascii {
+        let mut device = Ascii::new();
+        if let Some(script) = script {
+            device.script_file(script)?;
+        }
+
+        run(&memory, device, &patches, record, replay, dump)
+    } else {
+        run(&memory, Console, &patches, record, replay, dump)
+    }
+}
@@ -0,0 +1,136 @@
+//! Poking values into a program's memory, either when it's loaded or
+//! partway through a run, and searching memory for the cells to poke.
+//!
+//! A patch file has one `address=value` per line, or `start..end=value`
+//! to fill a range (end excluded). Lines apply at load time until a
+//! directive changes when the following lines apply:
+//!
+//! ```text
+//! @load      as the program is loaded
+//! @step N    once N instructions have run
+//! @input N   just before the Nth input (from 0) is read
+//! @always    before every instruction, pinning the cell
+//! ```
+//!
+//! Blank lines and `#` comments are ignored.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum When {
+    Load,
+    Step(u64),
+    Input(u64),
+    Always,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Patch {
+    pub addr: usize,
+    pub value: Cell,
+    pub when: When,
+}
+
+impl Patch {
+    pub fn new(addr: usize, value: Cell) -> Self {
+        Patch {
+            addr,
+            value,
+            when: When::Load,
+        }
+    }
+}
+
+pub fn parse(data: &str) -> Result<Vec<Patch>, String> {
+    let mut patches = Vec::new();
+    let mut when = When::Load;
+
+    for (n, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |what: &str| format!("line {}: {}: {}", n + 1, what, line);
+
+        if let Some(directive) = line.strip_prefix('@') {
+            let fields: Vec<&str> = directive.split_whitespace().collect();
+            let count = || {
+                fields
+                    .get(1)
+                    .and_then(|f| f.parse().ok())
+                    .ok_or_else(|| error("expected a count"))
+            };
+
+            when = match fields.first() {
+                Some(&"load") => When::Load,
+                Some(&"always") => When::Always,
+                Some(&"step") => When::Step(count()?),
+                Some(&"input") => When::Input(count()?),
+                _ => return Err(error("unknown directive")),
+            };
+            continue;
+        }
+
+        let (addrs, value) = match line.find('=') {
+            Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+            None => return Err(error("expected address=value")),
+        };
+        let value: Cell = value.parse().map_err(|_| error("bad value"))?;
+
+        let (start, end) = match addrs.find("..") {
+            Some(i) => (&addrs[..i], &addrs[i + 2..]),
+            None => (addrs, ""),
+        };
+        let start: usize = start.parse().map_err(|_| error("bad address"))?;
+        let end: usize = if end.is_empty() {
+            start + 1
+        } else {
+            end.parse().map_err(|_| error("bad address"))?
+        };
+
+        for addr in start..end {
+            patches.push(Patch { addr, value, when });
+        }
+    }
+
+    Ok(patches)
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<Patch>> {
+    parse(&fs::read_to_string(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Narrows down which cell holds some value by checking memory at
+/// different points in a run, the way a cheat finder does.
+pub struct Search {
+    candidates: Vec<usize>,
+}
+
+impl Search {
+    /// Starts with every cell that currently holds `value`.
+    pub fn new(memory: &[Cell], value: Cell) -> Self {
+        Search {
+            candidates: memory
+                .iter()
+                .enumerate()
+                .filter(|&(_, &v)| v == value)
+                .map(|(addr, _)| addr)
+                .collect(),
+        }
+    }
+
+    /// Drops candidates that don't hold `value` now.
+    pub fn refine(&mut self, memory: &[Cell], value: Cell) {
+        self.candidates
+            .retain(|&addr| memory.get(addr).cloned().unwrap_or(0) == value);
+    }
+
+    pub fn candidates(&self) -> &[usize] {
+        &self.candidates
+    }
+}
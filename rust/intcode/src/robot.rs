@@ -0,0 +1,243 @@
+//! Robots that move around a grid at a program's direction, for the
+//! programs that take camera or status readings in and give movement out.
+//!
+//! Positions and headings come from `geometry`, so `y` grows downwards.
+
+use std::collections::VecDeque;
+
+use geometry::{Point, Sparse};
+
+use crate::{Cell, Device, Intcode};
+
+pub use geometry::{Heading, Turn};
+
+/// What the program can tell the robot to do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Sets the panel under the robot.
+    Paint(Cell),
+    Turn(Turn),
+    Face(Heading),
+    /// Moves one step the way the robot faces.
+    Move,
+    /// Records what's in the way without moving, like a wall.
+    Blocked(Cell),
+}
+
+/// Turns program output into commands, and headings into input for the
+/// programs the robot has to tell where it's going.
+pub trait Decoder {
+    /// Takes one output value, returning commands once it has enough.
+    fn decode(&mut self, value: Cell) -> Vec<Command>;
+
+    fn encode(&self, heading: Heading) -> Cell {
+        panic!("can't tell the program to go {}", heading.name());
+    }
+}
+
+/// Pairs of colour then turn, with 0 turning left and 1 right, after
+/// which the robot moves on. Day 11's hull painter.
+#[derive(Clone, Debug, Default)]
+pub struct PaintTurn {
+    colour: Option<Cell>,
+}
+
+impl Decoder for PaintTurn {
+    fn decode(&mut self, value: Cell) -> Vec<Command> {
+        match self.colour.take() {
+            None => {
+                self.colour = Some(value);
+                Vec::new()
+            }
+            Some(colour) => {
+                let turn = match value {
+                    0 => Turn::Left,
+                    1 => Turn::Right,
+                    x => panic!("invalid turn: {}", x),
+                };
+                vec![Command::Paint(colour), Command::Turn(turn), Command::Move]
+            }
+        }
+    }
+}
+
+/// Numbers for each heading, in the order of `Heading::ALL`.
+pub type Codes = [Cell; 4];
+
+/// North, south, west and east as 1 to 4.
+pub const COMPASS: Codes = [1, 4, 2, 3];
+
+fn heading(codes: &Codes, value: Cell) -> Heading {
+    match codes.iter().position(|&c| c == value) {
+        Some(i) => Heading::ALL[i],
+        None => panic!("invalid direction: {}", value),
+    }
+}
+
+/// Each output is a direction to face and move in.
+#[derive(Clone, Debug)]
+pub struct Absolute {
+    pub codes: Codes,
+}
+
+impl Decoder for Absolute {
+    fn decode(&mut self, value: Cell) -> Vec<Command> {
+        vec![Command::Face(heading(&self.codes, value)), Command::Move]
+    }
+
+    fn encode(&self, heading: Heading) -> Cell {
+        self.codes[heading as usize]
+    }
+}
+
+/// The robot says which way it's going and the program answers with
+/// what it found: `blocked` if it couldn't move, or anything else to move
+/// there and mark it with that value.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub codes: Codes,
+    pub blocked: Cell,
+}
+
+impl Report {
+    pub fn new(codes: Codes) -> Self {
+        Report { codes, blocked: 0 }
+    }
+}
+
+impl Decoder for Report {
+    fn decode(&mut self, value: Cell) -> Vec<Command> {
+        if value == self.blocked {
+            vec![Command::Blocked(value)]
+        } else {
+            vec![Command::Move, Command::Paint(value)]
+        }
+    }
+
+    fn encode(&self, heading: Heading) -> Cell {
+        self.codes[heading as usize]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Robot<D> {
+    pub pos: Point,
+    pub heading: Heading,
+    /// Everything painted or found. Panels never touched aren't here.
+    pub grid: Sparse<Cell>,
+    /// Moves to give the program when it asks for input. The camera
+    /// answers once they run out.
+    pub moves: VecDeque<Heading>,
+    /// Commands carried out so far.
+    pub commands: usize,
+    pub decoder: D,
+}
+
+impl<D: Decoder> Robot<D> {
+    pub fn new(decoder: D) -> Self {
+        Robot {
+            pos: Point::ORIGIN,
+            heading: Heading::Up,
+            grid: Sparse::new(),
+            moves: VecDeque::new(),
+            commands: 0,
+            decoder,
+        }
+    }
+
+    /// The panel under the robot, with untouched panels as 0.
+    pub fn look(&self) -> Cell {
+        self.grid.get(self.pos).cloned().unwrap_or(0)
+    }
+
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::Paint(value) => {
+                self.grid.insert(self.pos, value);
+            }
+            Command::Turn(turn) => self.heading = self.heading.turn(turn),
+            Command::Face(heading) => self.heading = heading,
+            Command::Move => self.pos = self.heading.step(self.pos),
+            Command::Blocked(value) => {
+                self.grid.insert(self.heading.step(self.pos), value);
+            }
+        }
+        self.commands += 1;
+    }
+}
+
+impl<D: Decoder> Device for Robot<D> {
+    fn read(&mut self) -> Cell {
+        match self.moves.pop_front() {
+            Some(heading) => {
+                self.heading = heading;
+                self.decoder.encode(heading)
+            }
+            None => self.look(),
+        }
+    }
+
+    fn write(&mut self, value: Cell) {
+        for command in self.decoder.decode(value) {
+            self.apply(command);
+        }
+    }
+}
+
+/// What `explore` found.
+#[derive(Clone, Debug, Default)]
+pub struct Map {
+    /// Every position tried and what was there. The start is included as
+    /// whatever the robot had under it.
+    pub grid: Sparse<Cell>,
+    /// The fewest moves to each position that could be reached.
+    pub distance: Sparse<usize>,
+}
+
+/// Maps everything reachable from where the robot is by trying each way
+/// from each new position, breadth first. Every attempt runs on its own
+/// copy of the machine, so nothing ever has to walk back.
+pub fn explore<D: Decoder + Clone>(cpu: &Intcode<Robot<D>>) -> Map {
+    let start = cpu.device.pos;
+    let mut map = Map::default();
+    map.grid.insert(start, cpu.device.look());
+    map.distance.insert(start, 0);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(cpu.clone());
+
+    while let Some(cpu) = queue.pop_front() {
+        let from = cpu.device.pos;
+        let distance = map.distance[from];
+
+        for &heading in &Heading::ALL {
+            let to = heading.step(from);
+            if map.grid.contains(to) {
+                continue;
+            }
+
+            let mut next = cpu.clone();
+            next.device.moves.push_back(heading);
+            let commands = next.device.commands;
+            while next.device.commands == commands {
+                if !next.step() {
+                    break;
+                }
+            }
+
+            match next.device.grid.get(to) {
+                Some(&value) => {
+                    map.grid.insert(to, value);
+                }
+                None => continue,
+            }
+
+            if next.device.pos == to {
+                map.distance.insert(to, distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    map
+}
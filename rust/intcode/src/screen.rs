@@ -0,0 +1,132 @@
+//! A screen for programs that draw by outputting `x, y, tile` triplets.
+//!
+//! Tiles go into a sparse grid. Some coordinates can be set aside as
+//! registers instead, like day 13's score at `(-1, 0)`, and hold the
+//! last value written there without drawing anything.
+
+use std::collections::HashMap;
+
+use geometry::{Bounds, Point, Sparse};
+
+use crate::{Cell, Device};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Update {
+    /// A tile was drawn. `before` is `None` the first time.
+    Tile {
+        x: Cell,
+        y: Cell,
+        before: Option<Cell>,
+        after: Cell,
+    },
+    /// A register was written.
+    Register { x: Cell, y: Cell, value: Cell },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Screen {
+    tiles: Sparse<Cell>,
+    registers: HashMap<Point, Option<Cell>>,
+    palette: HashMap<Cell, char>,
+    pending: Vec<Cell>,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets `(x, y)` aside as a register.
+    pub fn register(mut self, x: Cell, y: Cell) -> Self {
+        self.registers.insert(Point::new(x, y), None);
+        self
+    }
+
+    /// Sets the characters `render` draws tiles with.
+    pub fn palette(mut self, palette: &[(Cell, char)]) -> Self {
+        self.palette.extend(palette.iter().cloned());
+        self
+    }
+
+    /// Takes the next value of a triplet, returning what changed once
+    /// the triplet is complete.
+    pub fn draw(&mut self, value: Cell) -> Option<Update> {
+        self.pending.push(value);
+        if self.pending.len() < 3 {
+            return None;
+        }
+
+        let (x, y, value) = (self.pending[0], self.pending[1], self.pending[2]);
+        self.pending.clear();
+
+        if let Some(register) = self.registers.get_mut(&Point::new(x, y)) {
+            *register = Some(value);
+            Some(Update::Register { x, y, value })
+        } else {
+            let before = self.tiles.insert(Point::new(x, y), value);
+            Some(Update::Tile {
+                x,
+                y,
+                before,
+                after: value,
+            })
+        }
+    }
+
+    pub fn tile(&self, x: Cell, y: Cell) -> Option<Cell> {
+        self.tiles.get(Point::new(x, y)).cloned()
+    }
+
+    pub fn tiles(&self) -> &Sparse<Cell> {
+        &self.tiles
+    }
+
+    /// The last value written to a register, if any has been.
+    pub fn value(&self, x: Cell, y: Cell) -> Option<Cell> {
+        self.registers.get(&Point::new(x, y)).cloned().flatten()
+    }
+
+    pub fn count(&self, tile: Cell) -> usize {
+        self.tiles.values().filter(|&&t| t == tile).count()
+    }
+
+    /// Where the first tile of a kind is, for things there's only one of.
+    pub fn find(&self, tile: Cell) -> Option<Point> {
+        self.tiles
+            .iter()
+            .find(|&(_, &t)| t == tile)
+            .map(|(pos, _)| pos)
+    }
+
+    /// The box around everything drawn.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.tiles.bounds()
+    }
+
+    /// The character for a tile: from the palette, a digit for small
+    /// tiles missing from it, or `?`.
+    pub fn glyph(&self, tile: Cell) -> char {
+        match self.palette.get(&tile) {
+            Some(&c) => c,
+            None if (0..10).contains(&tile) => (b'0' + tile as u8) as char,
+            None => '?',
+        }
+    }
+
+    /// Draws the grid as text, leaving cells never drawn blank and
+    /// trimming the ends of rows.
+    pub fn render(&self) -> String {
+        self.tiles.render(|&t| self.glyph(t))
+    }
+}
+
+/// For programs that only draw. Output goes to the screen.
+impl Device for Screen {
+    fn read(&mut self) -> Cell {
+        panic!("screen has no input");
+    }
+
+    fn write(&mut self, value: Cell) {
+        self.draw(value);
+    }
+}
@@ -0,0 +1,134 @@
+//! Recording and replaying the I/O a program does, so a bad run can be
+//! reproduced without its device.
+//!
+//! A session file has one event per line: the instruction count when it
+//! happened, `in` or `out`, and the value, e.g. `1234 in -1`.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use crate::Cell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Input(u64, Cell),
+    Output(u64, Cell),
+}
+
+impl Event {
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 3 {
+            return None;
+        }
+
+        let step = fields[0].parse().ok()?;
+        let value = fields[2].parse().ok()?;
+        match fields[1] {
+            "in" => Some(Event::Input(step, value)),
+            "out" => Some(Event::Output(step, value)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Input(step, value) => write!(f, "{} in {}", step, value),
+            Event::Output(step, value) => write!(f, "{} out {}", step, value),
+        }
+    }
+}
+
+/// Reads a session file, skipping blank lines and `#` comments.
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<Event>> {
+    let data = fs::read_to_string(path)?;
+    let mut events = Vec::new();
+
+    for (n, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match Event::parse(line) {
+            Some(event) => events.push(event),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: malformed event: {}", n + 1, line),
+                ))
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn Write>> {
+    Ok(Box::new(BufWriter::new(File::create(path)?)))
+}
+
+pub(crate) enum Session {
+    Record(Box<dyn Write>),
+    Replay(VecDeque<Event>),
+}
+
+impl Session {
+    /// Returns the recorded input for this step when replaying.
+    pub fn input(&mut self, step: u64) -> Option<Cell> {
+        match self {
+            Session::Record(_) => None,
+            Session::Replay(events) => match events.pop_front() {
+                Some(Event::Input(s, value)) if s == step => Some(value),
+                Some(expected) => diverged(step, expected, "input"),
+                None => panic!("replay diverged at step {}: ran past the end", step),
+            },
+        }
+    }
+
+    pub fn log_input(&mut self, step: u64, value: Cell) {
+        if let Session::Record(output) = self {
+            writeln!(output, "{}", Event::Input(step, value)).unwrap();
+        }
+    }
+
+    pub fn output(&mut self, step: u64, value: Cell) {
+        match self {
+            Session::Record(output) => {
+                writeln!(output, "{}", Event::Output(step, value)).unwrap();
+            }
+            Session::Replay(events) => {
+                let actual = Event::Output(step, value);
+                match events.pop_front() {
+                    Some(expected) if expected == actual => (),
+                    Some(expected) => diverged(step, expected, &actual.to_string()),
+                    None => panic!("replay diverged at step {}: ran past the end", step),
+                }
+            }
+        }
+    }
+
+    pub fn finish(&mut self, step: u64) {
+        match self {
+            Session::Record(output) => output.flush().unwrap(),
+            Session::Replay(events) => {
+                if let Some(expected) = events.front() {
+                    diverged(step, *expected, "halt");
+                }
+            }
+        }
+    }
+}
+
+fn diverged(step: u64, expected: Event, actual: &str) -> ! {
+    panic!(
+        "replay diverged at step {}: expected {}, got {}",
+        step, expected, actual
+    );
+}
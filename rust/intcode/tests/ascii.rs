@@ -0,0 +1,32 @@
+use std::io::Cursor;
+
+use intcode::{Ascii, Cell, Intcode};
+
+/// Echoes one line of input back, then reports 1000.
+const ECHO: [Cell; 15] = [
+    3, 100, 4, 100, 1008, 100, 10, 101, 1006, 101, 0, 104, 1000, 99, 0,
+];
+
+fn run(input: &str, script: &str) -> String {
+    let mut device = Ascii::with(Cursor::new(input.to_string()), Vec::new());
+    device.script(script);
+
+    let mut cpu = Intcode::new(&ECHO, device);
+    cpu.run();
+    String::from_utf8(cpu.device.output().clone()).unwrap()
+}
+
+#[test]
+fn line_in_text_out() {
+    assert_eq!(run("hello\n", ""), "hello\n1000\n");
+}
+
+#[test]
+fn script_is_echoed() {
+    assert_eq!(run("", "hi\n"), "hi\nhi\n1000\n");
+}
+
+#[test]
+fn script_comes_before_input() {
+    assert_eq!(run("typed\n", "scripted"), "scripted\nscripted\n1000\n");
+}
@@ -0,0 +1,11 @@
+use intcode::conformance::{self, Feature};
+use intcode::{Buffer, Intcode};
+
+#[test]
+fn conformance() {
+    conformance::check(&Feature::ALL, |case| {
+        let mut cpu = Intcode::new(&case.program, Buffer::new(&case.input));
+        cpu.run();
+        (cpu.device.output.clone(), cpu.memory().to_vec())
+    });
+}
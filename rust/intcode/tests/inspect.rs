@@ -0,0 +1,39 @@
+use intcode::inspect::{self, Change};
+use intcode::{Buffer, Intcode};
+
+#[test]
+fn diff_counts_missing_memory_as_zero() {
+    let changes = inspect::diff(&[1, 2, 3], &[1, 5, 3, 0, 7]);
+    assert_eq!(
+        changes,
+        vec![
+            Change {
+                addr: 1,
+                before: 2,
+                after: 5
+            },
+            Change {
+                addr: 4,
+                before: 0,
+                after: 7
+            },
+        ]
+    );
+}
+
+#[test]
+fn writes_are_counted_per_address() {
+    // Counts down from 3, writing the counter each time round.
+    let program = [1001, 9, -1, 9, 1005, 9, 0, 99, 0, 3];
+    let mut cpu = Intcode::new(&program, Buffer::default());
+    cpu.count_writes();
+    cpu.run();
+
+    let mut expected = vec![0; 10];
+    expected[9] = 3;
+    assert_eq!(cpu.writes().unwrap(), &expected[..]);
+    assert_eq!(
+        inspect::writes_table(&expected),
+        "    addr     writes\n       9          3 ########################################\n"
+    );
+}
@@ -0,0 +1,128 @@
+use intcode::patch::{self, Patch, Search, When};
+use intcode::{Buffer, Intcode};
+
+/// Adds pairs of numbers until it reads a zero. The add is at 7.
+const ADDER: [i64; 17] = [
+    3, 20, 1006, 20, 16, 3, 21, 1, 20, 21, 22, 4, 22, 1105, 1, 0, 99,
+];
+
+#[test]
+fn parse_directives_and_ranges() {
+    let patches = patch::parse(
+        "# quarters\n\
+         0=2\n\
+         \n\
+         @step 10\n\
+         5..8=-1\n\
+         @input 3\n\
+         9 = 4\n\
+         @always\n\
+         12=0\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        patches,
+        vec![
+            Patch::new(0, 2),
+            Patch {
+                addr: 5,
+                value: -1,
+                when: When::Step(10)
+            },
+            Patch {
+                addr: 6,
+                value: -1,
+                when: When::Step(10)
+            },
+            Patch {
+                addr: 7,
+                value: -1,
+                when: When::Step(10)
+            },
+            Patch {
+                addr: 9,
+                value: 4,
+                when: When::Input(3)
+            },
+            Patch {
+                addr: 12,
+                value: 0,
+                when: When::Always
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_errors_name_the_line() {
+    assert_eq!(
+        patch::parse("0=2\n1:3").unwrap_err(),
+        "line 2: expected address=value: 1:3"
+    );
+    assert_eq!(patch::parse("x=1").unwrap_err(), "line 1: bad address: x=1");
+    assert_eq!(
+        patch::parse("@step").unwrap_err(),
+        "line 1: expected a count: @step"
+    );
+    assert_eq!(
+        patch::parse("@later").unwrap_err(),
+        "line 1: unknown directive: @later"
+    );
+}
+
+#[test]
+fn load_patch() {
+    let mut cpu = Intcode::new(&ADDER, Buffer::new(&[2, 3, 10, -4, 0]));
+    cpu.patch(&[Patch::new(7, 2)]);
+    cpu.run();
+    assert_eq!(cpu.device.output, vec![6, -40]);
+}
+
+#[test]
+fn input_patch() {
+    let mut cpu = Intcode::new(&ADDER, Buffer::new(&[2, 3, 10, -4, 0]));
+    cpu.patch(&[Patch {
+        addr: 7,
+        value: 2,
+        when: When::Input(2),
+    }]);
+    cpu.run();
+    assert_eq!(cpu.device.output, vec![5, -40]);
+}
+
+#[test]
+fn step_patch() {
+    let mut cpu = Intcode::new(&ADDER, Buffer::new(&[2, 3, 10, -4, 0]));
+    cpu.patch(&[Patch {
+        addr: 7,
+        value: 2,
+        when: When::Step(5),
+    }]);
+    cpu.run();
+    assert_eq!(cpu.device.output, vec![5, -40]);
+}
+
+#[test]
+fn always_patch_pins_a_cell() {
+    let mut cpu = Intcode::new(&ADDER, Buffer::new(&[2, 3, 10, -4, 0]));
+    cpu.patch(&[Patch {
+        addr: 21,
+        value: 100,
+        when: When::Always,
+    }]);
+    cpu.run();
+    assert_eq!(cpu.device.output, vec![102, 110]);
+}
+
+#[test]
+fn search_narrows_candidates() {
+    let mut search = Search::new(&[5, 1, 5, 5, 0], 5);
+    assert_eq!(search.candidates(), &[0, 2, 3]);
+
+    search.refine(&[5, 1, 6, 6, 0], 6);
+    assert_eq!(search.candidates(), &[2, 3]);
+
+    search.refine(&[5, 1, 7, 6], 7);
+    assert_eq!(search.candidates(), &[2]);
+}
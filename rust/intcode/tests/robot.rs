@@ -0,0 +1,74 @@
+use intcode::robot::{self, Absolute, Heading, PaintTurn, Report, Robot, Turn, COMPASS};
+use intcode::{Device, Intcode};
+
+use geometry::Point;
+
+#[test]
+fn headings_turn() {
+    assert_eq!(Heading::Up.turn(Turn::Left), Heading::Left);
+    assert_eq!(Heading::Left.turn(Turn::Right), Heading::Up);
+    assert_eq!(Heading::Right.turn(Turn::Around), Heading::Left);
+    assert_eq!(Heading::Down.step(Point::new(3, 4)), Point::new(3, 5));
+}
+
+#[test]
+fn paint_and_turn() {
+    // The example from day 11.
+    let mut robot = Robot::new(PaintTurn::default());
+    for &(colour, turn) in &[(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)] {
+        robot.write(colour);
+        robot.write(turn);
+    }
+
+    assert_eq!(robot.grid.len(), 6);
+    assert_eq!(robot.grid.values().filter(|&&c| c == 1).count(), 4);
+    assert_eq!(robot.pos, Point::new(0, -1));
+    assert_eq!(robot.heading, Heading::Left);
+    assert_eq!(robot.look(), 0);
+}
+
+#[test]
+fn absolute_directions() {
+    // Goes east twice and north once.
+    let program = [104, 4, 104, 4, 104, 1, 99];
+    let mut cpu = Intcode::new(&program, Robot::new(Absolute { codes: COMPASS }));
+    cpu.run();
+
+    assert_eq!(cpu.device.pos, Point::new(2, -1));
+    assert_eq!(cpu.device.heading, Heading::Up);
+}
+
+/// A corridor running east from the start. The far end, three along,
+/// reports 2.
+const CORRIDOR: [i64; 61] = [
+    3, 100, 1008, 100, 4, 101, 1005, 101, 21, 1008, 100, 3, 101, 1005, 101, 44, 104, 0, 1105, 1, 0,
+    1008, 102, 3, 101, 1005, 101, 16, 1001, 102, 1, 102, 1008, 102, 3, 101, 1005, 101, 56, 104, 1,
+    1105, 1, 0, 1006, 102, 16, 1001, 102, -1, 102, 104, 1, 1105, 1, 0, 104, 2, 1105, 1, 0,
+];
+
+#[test]
+fn report_moves_or_blocks() {
+    let mut cpu = Intcode::new(&CORRIDOR, Robot::new(Report::new(COMPASS)));
+    cpu.device
+        .moves
+        .extend(&[Heading::Left, Heading::Right, Heading::Up]);
+    while cpu.device.commands < 4 && cpu.step() {}
+
+    assert_eq!(cpu.device.pos, Point::new(1, 0));
+    assert_eq!(cpu.device.grid.get(Point::new(-1, 0)), Some(&0));
+    assert_eq!(cpu.device.grid.get(Point::new(1, 0)), Some(&1));
+    assert_eq!(cpu.device.grid.get(Point::new(1, -1)), Some(&0));
+}
+
+#[test]
+fn explore_maps_the_corridor() {
+    let cpu = Intcode::new(&CORRIDOR, Robot::new(Report::new(COMPASS)));
+    let map = robot::explore(&cpu);
+
+    assert_eq!(map.grid.len(), 4 + 4 * 2 + 2);
+    assert_eq!(map.grid[Point::new(3, 0)], 2);
+    assert_eq!(map.grid[Point::new(4, 0)], 0);
+    assert_eq!(map.grid[Point::new(-1, 0)], 0);
+    assert_eq!(map.distance[Point::new(3, 0)], 3);
+    assert_eq!(map.distance.len(), 4);
+}
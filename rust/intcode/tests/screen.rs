@@ -0,0 +1,95 @@
+use intcode::screen::{Screen, Update};
+use intcode::Intcode;
+
+use geometry::{Bounds, Point};
+
+#[test]
+fn triplets_draw_tiles() {
+    let mut screen = Screen::new();
+    assert_eq!(screen.draw(1), None);
+    assert_eq!(screen.draw(2), None);
+    assert_eq!(
+        screen.draw(3),
+        Some(Update::Tile {
+            x: 1,
+            y: 2,
+            before: None,
+            after: 3
+        })
+    );
+    assert_eq!(
+        [6, 5, 4, 1, 2, 0]
+            .iter()
+            .filter_map(|&v| screen.draw(v))
+            .last(),
+        Some(Update::Tile {
+            x: 1,
+            y: 2,
+            before: Some(3),
+            after: 0
+        })
+    );
+
+    assert_eq!(screen.tile(1, 2), Some(0));
+    assert_eq!(screen.tile(6, 5), Some(4));
+    assert_eq!(screen.tile(0, 0), None);
+    assert_eq!(screen.count(4), 1);
+    assert_eq!(screen.find(4), Some(Point::new(6, 5)));
+    assert_eq!(
+        screen.bounds(),
+        Some(Bounds::new(Point::new(1, 2), Point::new(6, 5)))
+    );
+}
+
+#[test]
+fn registers_are_not_drawn() {
+    let mut screen = Screen::new().register(-1, 0);
+    assert_eq!(screen.value(-1, 0), None);
+
+    let updates: Vec<Update> = [-1, 0, 12345, 0, 0, 1]
+        .iter()
+        .filter_map(|&v| screen.draw(v))
+        .collect();
+
+    assert_eq!(
+        updates[0],
+        Update::Register {
+            x: -1,
+            y: 0,
+            value: 12345
+        }
+    );
+    assert_eq!(screen.value(-1, 0), Some(12345));
+    assert_eq!(screen.tile(-1, 0), None);
+    assert_eq!(
+        screen.bounds(),
+        Some(Bounds::new(Point::ORIGIN, Point::ORIGIN))
+    );
+}
+
+#[test]
+fn render_with_palette() {
+    let mut screen = Screen::new().palette(&[(0, '.'), (1, '#')]);
+    for &v in &[0, 0, 1, 2, 0, 1, 1, 1, 0, 2, 1, 7, 4, 1, -3] {
+        screen.draw(v);
+    }
+
+    // Cells never drawn stay blank and tiles missing from the palette
+    // show as digits, or ? when they won't fit in one.
+    assert_eq!(screen.render(), "# #\n .7 ?\n");
+}
+
+#[test]
+fn runs_a_drawing_program() {
+    // Draws a diagonal of three tiles.
+    let program = [
+        104, 0, 104, 0, 104, 1, 104, 1, 104, 1, 104, 2, 104, 2, 104, 2, 104, 3, 99,
+    ];
+    let mut cpu = Intcode::new(
+        &program,
+        Screen::new().palette(&[(1, 'a'), (2, 'b'), (3, 'c')]),
+    );
+    cpu.run();
+
+    assert_eq!(cpu.device.render(), "a\n b\n  c\n");
+}
@@ -0,0 +1,58 @@
+use std::env;
+use std::fs;
+
+use intcode::session::{self, Event};
+use intcode::{Buffer, Intcode};
+
+/// Adds pairs of numbers until it reads a zero.
+const ADDER: [i64; 17] = [
+    3, 20, 1006, 20, 16, 3, 21, 1, 20, 21, 22, 4, 22, 1105, 1, 0, 99,
+];
+
+#[test]
+fn record_then_replay() {
+    let path = env::temp_dir().join(format!("intcode-session-{}.txt", std::process::id()));
+
+    let mut cpu = Intcode::new(&ADDER, Buffer::new(&[2, 3, 10, -4, 0]));
+    cpu.record(session::create(&path).unwrap());
+    cpu.run();
+    assert_eq!(cpu.device.output, vec![5, 6]);
+
+    let events = session::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            Event::Input(0, 2),
+            Event::Input(2, 3),
+            Event::Output(4, 5),
+            Event::Input(6, 10),
+            Event::Input(8, -4),
+            Event::Output(10, 6),
+            Event::Input(12, 0),
+        ]
+    );
+
+    let mut cpu = Intcode::new(&ADDER, Buffer::default());
+    cpu.replay(events);
+    cpu.run();
+    assert_eq!(cpu.device.output, vec![5, 6]);
+}
+
+#[test]
+#[should_panic(expected = "replay diverged at step 4: expected 4 out 6, got 4 out 5")]
+fn replay_reports_divergence() {
+    let events = vec![Event::Input(0, 2), Event::Input(2, 3), Event::Output(4, 6)];
+
+    let mut cpu = Intcode::new(&ADDER, Buffer::default());
+    cpu.replay(events);
+    cpu.run();
+}
+
+#[test]
+#[should_panic(expected = "replay diverged at step 2: expected 4 in 0, got halt")]
+fn replay_reports_early_halt() {
+    let mut cpu = Intcode::new(&ADDER, Buffer::default());
+    cpu.replay(vec![Event::Input(0, 0), Event::Input(4, 0)]);
+    cpu.run();
+}
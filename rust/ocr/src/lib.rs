@@ -0,0 +1,165 @@
+//! Reading the block letters some puzzles draw their answers in, so the
+//! answer comes out as text instead of a picture to squint at.
+
+use std::error;
+use std::fmt;
+
+pub mod font;
+
+use crate::font::{Font, FONTS};
+
+/// Which pixels are inked, row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    ink: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Asks `ink` about every pixel.
+    pub fn new<F: Fn(usize, usize) -> bool>(width: usize, height: usize, ink: F) -> Self {
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(ink(x, y));
+            }
+        }
+        Bitmap {
+            width,
+            height,
+            ink: pixels,
+        }
+    }
+
+    /// `#` is ink and anything else isn't. Short lines are padded out.
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        Bitmap::new(width, lines.len(), |x, y| lines[y].get(x) == Some(&'#'))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.ink[y * self.width + x]
+    }
+
+    fn column_is_blank(&self, x: usize, rows: &[usize]) -> bool {
+        rows.iter().all(|&y| !self.get(x, y))
+    }
+
+    fn row_is_blank(&self, y: usize) -> bool {
+        (0..self.width).all(|x| !self.get(x, y))
+    }
+
+    /// Columns `xs` of rows `ys`, as `#` and `.`.
+    fn text(&self, xs: &[usize], ys: &[usize]) -> String {
+        let rows: Vec<String> = ys
+            .iter()
+            .map(|&y| {
+                xs.iter()
+                    .map(|&x| if self.get(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        rows.join("\n")
+    }
+}
+
+/// A glyph that doesn't look like any letter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unknown {
+    /// Where its first column is in the bitmap.
+    pub column: usize,
+    /// As `#` and `.`, one row per line.
+    pub bitmap: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Letters of a height none of the fonts come in.
+    Height(usize),
+    /// Some glyphs weren't letters. `partial` has the rest, with `?` for
+    /// each of these.
+    Unknown {
+        partial: String,
+        glyphs: Vec<Unknown>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Height(height) => write!(f, "no font is {} pixels high", height),
+            Error::Unknown { partial, glyphs } => {
+                write!(f, "unknown glyphs in {:?}", partial)?;
+                for glyph in glyphs {
+                    write!(f, "\ncolumn {}:\n{}", glyph.column, glyph.bitmap)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+fn font(height: usize) -> Option<&'static Font> {
+    FONTS.iter().find(|font| font.height == height)
+}
+
+/// Reads the letters in `bitmap`. Blank rows above and below are ignored,
+/// the height picks the font, and letters are told apart by the blank
+/// columns between them.
+pub fn read(bitmap: &Bitmap) -> Result<String, Error> {
+    let rows: Vec<usize> = (0..bitmap.height())
+        .filter(|&y| !bitmap.row_is_blank(y))
+        .collect();
+    let rows: Vec<usize> = match (rows.first(), rows.last()) {
+        (Some(&top), Some(&bottom)) => (top..=bottom).collect(),
+        _ => return Ok(String::new()),
+    };
+    let font = font(rows.len()).ok_or(Error::Height(rows.len()))?;
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    let mut glyph: Vec<usize> = Vec::new();
+    for x in 0..=bitmap.width() {
+        if x < bitmap.width() && !bitmap.column_is_blank(x, &rows) {
+            glyph.push(x);
+            continue;
+        }
+        if glyph.is_empty() {
+            continue;
+        }
+
+        let drawn = bitmap.text(&glyph, &rows);
+        match font.glyphs.iter().find(|(_, g)| *g == drawn) {
+            Some(&(c, _)) => text.push(c),
+            None => {
+                text.push('?');
+                unknown.push(Unknown {
+                    column: glyph[0],
+                    bitmap: drawn,
+                });
+            }
+        }
+        glyph.clear();
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(Error::Unknown {
+            partial: text,
+            glyphs: unknown,
+        })
+    }
+}
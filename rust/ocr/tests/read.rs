@@ -0,0 +1,94 @@
+use ocr::font::{Font, FONTS};
+use ocr::{read, Bitmap, Error, Unknown};
+
+/// Draws `text` in `font`, `gap` blank columns apart, with a blank border.
+fn draw(font: &Font, text: &str, gap: usize) -> String {
+    let mut rows = vec![String::from("."); font.height];
+    for c in text.chars() {
+        let (_, glyph) = font.glyphs.iter().find(|(g, _)| *g == c).unwrap();
+        for (row, line) in rows.iter_mut().zip(glyph.lines()) {
+            row.push_str(line);
+            row.push_str(&".".repeat(gap));
+        }
+    }
+    let blank = ".".repeat(rows[0].len());
+    format!("{}\n{}\n{}\n", blank, rows.join("\n"), blank)
+}
+
+#[test]
+fn fonts() {
+    for font in FONTS {
+        for (c, glyph) in font.glyphs {
+            let rows: Vec<&str> = glyph.lines().collect();
+            assert_eq!(rows.len(), font.height, "{}", c);
+            assert!(rows.iter().all(|r| r.len() == rows[0].len()), "{}", c);
+            assert!(rows.iter().any(|r| r.starts_with('#')), "{}", c);
+            assert!(rows.iter().any(|r| r.ends_with('#')), "{}", c);
+        }
+
+        let all: String = font.glyphs.iter().map(|(c, _)| c).collect();
+        let gap = font.height / 5;
+        assert_eq!(read(&Bitmap::from_text(&draw(font, &all, gap))), Ok(all));
+    }
+}
+
+#[test]
+fn day8() {
+    let text = " ##  ####  ##  #  #  ##\n\
+                #  # #    #  # #  # #  #\n\
+                #    ###  #    #  # #\n\
+                #    #    #    #  # # ##\n\
+                #  # #    #  # #  # #  #\n\
+                \x20##  #     ##   ##   ###\n";
+    assert_eq!(read(&Bitmap::from_text(text)), Ok("CFCUG".to_string()));
+}
+
+#[test]
+fn day11() {
+    let text = ".#....####.###...##..###..#.....##..####...\n\
+                .#....#....#..#.#..#.#..#.#....#..#....#...\n\
+                .#....###..#..#.#....#..#.#....#......#....\n\
+                .#....#....###..#....###..#....#.##..#.....\n\
+                .#....#....#....#..#.#....#....#..#.#......\n\
+                .####.####.#.....##..#....####..###.####...\n";
+    assert_eq!(read(&Bitmap::from_text(text)), Ok("LEPCPLGZ".to_string()));
+}
+
+#[test]
+fn bitmaps() {
+    let bitmap = Bitmap::new(3, 2, |x, y| x == y);
+    assert_eq!((bitmap.width(), bitmap.height()), (3, 2));
+    assert!(bitmap.get(1, 1));
+    assert!(!bitmap.get(2, 1));
+
+    assert_eq!(read(&Bitmap::from_text("\n...\n")), Ok(String::new()));
+}
+
+#[test]
+fn unknown_glyphs() {
+    let text = "#..#.#...#\n\
+                #..#.##..#\n\
+                ####.#.#.#\n\
+                #..#.#..##\n\
+                #..#.#...#\n\
+                #..#.#...#\n";
+    let e = read(&Bitmap::from_text(text)).unwrap_err();
+    assert_eq!(
+        e,
+        Error::Unknown {
+            partial: "H?".to_string(),
+            glyphs: vec![Unknown {
+                column: 5,
+                bitmap: "#...#\n##..#\n#.#.#\n#..##\n#...#\n#...#".to_string(),
+            }],
+        }
+    );
+    assert_eq!(
+        e.to_string(),
+        "unknown glyphs in \"H?\"\n\
+         column 5:\n\
+         #...#\n##..#\n#.#.#\n#..##\n#...#\n#...#"
+    );
+
+    assert_eq!(read(&Bitmap::from_text("#\n#\n#\n")), Err(Error::Height(3)));
+}
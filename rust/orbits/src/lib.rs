@@ -0,0 +1,31 @@
+//! Day 6's orbit maps: which body orbits which, as a tree.
+
+pub mod render;
+pub mod tree;
+pub mod validate;
+
+pub use crate::tree::{Body, Tree};
+pub use crate::validate::{validate, Problem, Report};
+
+/// Reads `A)B` lines, where `B` orbits `A`, as `(A, B)` pairs. Blank
+/// lines are skipped.
+pub fn parse(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = Vec::new();
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut names = line.split(')');
+        match (names.next(), names.next(), names.next()) {
+            (Some(a), Some(b), None) if !a.is_empty() && !b.is_empty() => {
+                pairs.push((a.to_string(), b.to_string()))
+            }
+            _ => return Err(format!("line {}: expected A)B: {}", n + 1, line)),
+        }
+    }
+
+    Ok(pairs)
+}
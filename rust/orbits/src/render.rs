@@ -0,0 +1,94 @@
+//! Ways to look at a whole orbit map: as a Graphviz graph, or as an
+//! indented tree of text.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::tree::{Body, Tree};
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The tree in Graphviz's DOT language, with an arrow from each body to
+/// whatever orbits it. With `highlight`, the path between those two
+/// bodies is picked out.
+pub fn dot(tree: &Tree, highlight: Option<(Body, Body)>) -> String {
+    let path = highlight.map_or_else(Vec::new, |(a, b)| tree.path(a, b));
+    // Each hop as (parent, child), whichever way the path goes over it.
+    let steps: HashSet<(Body, Body)> = path
+        .windows(2)
+        .map(|w| match tree.parent(w[0]) {
+            Some(p) if p == w[1] => (w[1], w[0]),
+            _ => (w[0], w[1]),
+        })
+        .collect();
+
+    let mut out = String::new();
+    writeln!(out, "digraph orbits {{").unwrap();
+    writeln!(out, "    node [shape=circle];").unwrap();
+    for &body in &path {
+        writeln!(
+            out,
+            "    {} [style=filled, fillcolor=gold];",
+            quote(tree.name(body))
+        )
+        .unwrap();
+    }
+
+    let mut stack = vec![tree.root()];
+    while let Some(body) = stack.pop() {
+        for &child in tree.children(body) {
+            write!(
+                out,
+                "    {} -> {}",
+                quote(tree.name(body)),
+                quote(tree.name(child))
+            )
+            .unwrap();
+            if steps.contains(&(body, child)) {
+                write!(out, " [color=red, penwidth=3]").unwrap();
+            }
+            writeln!(out, ";").unwrap();
+        }
+        stack.extend(tree.children(body).iter().rev());
+    }
+
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// The tree as indented text, one body to a line with how many bodies
+/// are in its subtree, counting itself.
+///
+/// With `fold`, bodies orbited by only one other are run together with it
+/// on one line, `A)B)C`, so long chains don't push everything off to the
+/// right. The count is then for the whole of what's under the first of
+/// them.
+pub fn text(tree: &Tree, fold: bool) -> String {
+    let sizes = tree.sizes();
+    let mut out = String::new();
+
+    let mut stack = vec![(tree.root(), 0)];
+    while let Some((head, level)) = stack.pop() {
+        let mut chain = vec![tree.name(head)];
+        let mut body = head;
+        while let (true, [only]) = (fold, tree.children(body)) {
+            body = *only;
+            chain.push(tree.name(body));
+        }
+
+        writeln!(
+            out,
+            "{:indent$}{} ({})",
+            "",
+            chain.join(")"),
+            sizes[head],
+            indent = level * 2
+        )
+        .unwrap();
+        stack.extend(tree.children(body).iter().rev().map(|&c| (c, level + 1)));
+    }
+
+    out
+}
@@ -0,0 +1,181 @@
+//! The orbit map built once, with every body's depth worked out up front
+//! so counting orbits and finding common ancestors don't have to walk
+//! all the way to the root each time.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+use crate::validate::{validate, Report};
+
+/// A body, by its index in the tree.
+pub type Body = usize;
+
+#[derive(Clone, Debug)]
+pub struct Tree {
+    names: Vec<String>,
+    index: HashMap<String, Body>,
+    parent: Vec<Option<Body>>,
+    children: Vec<Vec<Body>>,
+    /// Orbits each body is in, direct and indirect, which is how far it
+    /// is from the root.
+    depth: Vec<usize>,
+    root: Body,
+}
+
+impl Tree {
+    /// Builds the tree from `(A, B)` pairs where `B` orbits `A`, as long
+    /// as `validate` has nothing to say about them.
+    pub fn new(pairs: &[(String, String)]) -> Result<Self, Report> {
+        let report = validate(pairs, &[]);
+        if !report.is_ok() {
+            return Err(report);
+        }
+
+        let mut tree = Tree {
+            names: Vec::new(),
+            index: HashMap::new(),
+            parent: Vec::new(),
+            children: Vec::new(),
+            depth: Vec::new(),
+            root: 0,
+        };
+        for (a, b) in pairs {
+            let (a, b) = (tree.add(a), tree.add(b));
+            if tree.parent[b].is_none() {
+                tree.parent[b] = Some(a);
+                tree.children[a].push(b);
+            }
+        }
+        if let Some(root) = report.root {
+            tree.root = tree.index[&root];
+        }
+
+        tree.depth = vec![0; tree.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(tree.root);
+        while let Some(body) = queue.pop_front() {
+            for &child in &tree.children[body] {
+                tree.depth[child] = tree.depth[body] + 1;
+                queue.push_back(child);
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// Reads and builds the tree, with everything wrong with it in the
+    /// error.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Tree::new(&crate::parse(text)?).map_err(|report| report.to_string())
+    }
+
+    fn add(&mut self, name: &str) -> Body {
+        if let Some(&body) = self.index.get(name) {
+            return body;
+        }
+
+        let body = self.names.len();
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), body);
+        self.parent.push(None);
+        self.children.push(Vec::new());
+        body
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn body(&self, name: &str) -> Option<Body> {
+        self.index.get(name).cloned()
+    }
+
+    pub fn name(&self, body: Body) -> &str {
+        &self.names[body]
+    }
+
+    /// The one body that doesn't orbit anything.
+    pub fn root(&self) -> Body {
+        self.root
+    }
+
+    pub fn parent(&self, body: Body) -> Option<Body> {
+        self.parent[body]
+    }
+
+    /// What orbits `body` directly, in the order the map gave them.
+    pub fn children(&self, body: Body) -> &[Body] {
+        &self.children[body]
+    }
+
+    pub fn depth(&self, body: Body) -> usize {
+        self.depth[body]
+    }
+
+    /// How many bodies are in each body's subtree, counting itself.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![1; self.len()];
+        let mut deepest: Vec<Body> = (0..self.len()).collect();
+        deepest.sort_by_key(|&b| Reverse(self.depth[b]));
+        for body in deepest {
+            if let Some(parent) = self.parent[body] {
+                sizes[parent] += sizes[body];
+            }
+        }
+        sizes
+    }
+
+    /// Every orbit, direct and indirect.
+    pub fn total_orbits(&self) -> usize {
+        self.depth.iter().sum()
+    }
+
+    /// The nearest body both orbit, counting each as orbiting itself.
+    pub fn lca(&self, mut a: Body, mut b: Body) -> Body {
+        while self.depth[a] > self.depth[b] {
+            a = self.parent[a].unwrap();
+        }
+        while self.depth[b] > self.depth[a] {
+            b = self.parent[b].unwrap();
+        }
+        while a != b {
+            a = self.parent[a].unwrap();
+            b = self.parent[b].unwrap();
+        }
+        a
+    }
+
+    /// Hops between two bodies.
+    pub fn distance(&self, a: Body, b: Body) -> usize {
+        self.depth[a] + self.depth[b] - 2 * self.depth[self.lca(a, b)]
+    }
+
+    /// Every body from `a` to `b`, both included, up to where they meet
+    /// and back down.
+    pub fn path(&self, a: Body, b: Body) -> Vec<Body> {
+        let lca = self.lca(a, b);
+        let up = |mut body: Body| {
+            let mut bodies = Vec::new();
+            while body != lca {
+                bodies.push(body);
+                body = self.parent[body].unwrap();
+            }
+            bodies
+        };
+
+        let mut path = up(a);
+        path.push(lca);
+        path.extend(up(b).into_iter().rev());
+        path
+    }
+
+    /// Orbital transfers to get from what `a` orbits to what `b` orbits,
+    /// as day 6 counts them.
+    pub fn transfers(&self, a: Body, b: Body) -> Option<usize> {
+        Some(self.distance(self.parent(a)?, self.parent(b)?))
+    }
+}
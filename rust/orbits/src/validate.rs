@@ -0,0 +1,212 @@
+//! Checking an orbit map over before trusting it to be a tree, so a bad
+//! map says what's wrong with it instead of panicking or looping forever.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// A body the caller needs isn't in the map at all.
+    Missing(String),
+    /// A body orbits more than one other.
+    TwoParents { body: String, parents: Vec<String> },
+    /// Nothing is the root, as the map's empty or everything orbits
+    /// something.
+    NoRoot,
+    /// More than one body doesn't orbit anything, so the map is several
+    /// trees. The one with the most in it is taken as the root.
+    MultipleRoots(Vec<String>),
+    /// Bodies orbiting each other in a circle, each orbited by the one
+    /// after it.
+    Cycle(Vec<String>),
+    /// A body that never gets to the root, because what it orbits, directly
+    /// or not, is caught in a cycle. `cycle` is the first body in the
+    /// cycle going up from it. Every such body is listed, not just the
+    /// ones orbiting the cycle directly.
+    Orphaned { body: String, cycle: String },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Missing(body) => write!(f, "no body called {}", body),
+            Problem::TwoParents { body, parents } => {
+                let (last, rest) = parents.split_last().unwrap();
+                write!(f, "{} orbits {} and {}", body, rest.join(", "), last)
+            }
+            Problem::NoRoot => write!(f, "nothing to be the root"),
+            Problem::MultipleRoots(roots) => {
+                write!(f, "more than one root: {}", roots.join(", "))
+            }
+            Problem::Cycle(bodies) => {
+                write!(f, "orbits go round in a cycle: {})", bodies.join(")"))?;
+                write!(f, "{}", bodies[0])
+            }
+            Problem::Orphaned { body, cycle } => write!(
+                f,
+                "{} never gets to the root, as {} is caught in a cycle",
+                body, cycle
+            ),
+        }
+    }
+}
+
+/// What `validate` made of a map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// The body that doesn't orbit anything, or the biggest of them if
+    /// there are several.
+    pub root: Option<String>,
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// One problem per line.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, problem) in self.problems.iter().enumerate() {
+            if n > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `(A, B)` pairs, where `B` orbits `A`, make a single tree, and
+/// that every body in `required` is in it. Problems come out a kind at a
+/// time: missing bodies in the order `required` gives them, then bodies
+/// with two parents, then a missing root or extra roots, then cycles,
+/// then bodies cut off by a cycle. Within a kind they're in the order the
+/// bodies involved first appear in the map.
+pub fn validate(pairs: &[(String, String)], required: &[&str]) -> Report {
+    let mut names: Vec<&str> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (a, b) in pairs {
+        for name in &[a, b] {
+            if !index.contains_key(name.as_str()) {
+                index.insert(name, names.len());
+                names.push(name);
+            }
+        }
+    }
+
+    let mut problems: Vec<Problem> = required
+        .iter()
+        .filter(|name| !index.contains_key(*name))
+        .map(|name| Problem::Missing(name.to_string()))
+        .collect();
+
+    let mut parents: Vec<Vec<usize>> = vec![Vec::new(); names.len()];
+    for (a, b) in pairs {
+        let (a, b) = (index[a.as_str()], index[b.as_str()]);
+        if !parents[b].contains(&a) {
+            parents[b].push(a);
+        }
+    }
+    let owned = |bodies: &[usize]| bodies.iter().map(|&b| names[b].to_string()).collect();
+    for (body, these) in parents.iter().enumerate() {
+        if these.len() > 1 {
+            problems.push(Problem::TwoParents {
+                body: names[body].to_string(),
+                parents: owned(these),
+            });
+        }
+    }
+
+    // From here on each body just orbits the first one the map gave it.
+    let parent: Vec<Option<usize>> = parents.iter().map(|p| p.first().copied()).collect();
+    let mut children = vec![Vec::new(); names.len()];
+    for (body, p) in parent.iter().enumerate() {
+        if let Some(p) = *p {
+            children[p].push(body);
+        }
+    }
+
+    // Everything under each root, and how much of it there is.
+    let roots: Vec<usize> = (0..names.len()).filter(|&b| parent[b].is_none()).collect();
+    let mut reached = vec![false; names.len()];
+    let mut sizes = Vec::with_capacity(roots.len());
+    for &root in &roots {
+        let mut size = 0;
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(body) = queue.pop_front() {
+            reached[body] = true;
+            size += 1;
+            queue.extend(&children[body]);
+        }
+        sizes.push(size);
+    }
+
+    // The first of the biggest.
+    let root = (0..roots.len())
+        .rev()
+        .max_by_key(|&r| sizes[r])
+        .map(|r| names[roots[r]].to_string());
+    match roots.len() {
+        0 => problems.push(Problem::NoRoot),
+        1 => (),
+        _ => problems.push(Problem::MultipleRoots(owned(&roots))),
+    }
+
+    // Whatever no root reached is either in a cycle or hanging off one.
+    // Walk up from each until the walk runs into itself.
+    let mut in_cycle = vec![false; names.len()];
+    let mut walked = reached.clone();
+    for start in 0..names.len() {
+        let mut walk = Vec::new();
+        let mut body = start;
+        while !walked[body] {
+            walked[body] = true;
+            walk.push(body);
+            body = parent[body].unwrap();
+        }
+        if let Some(at) = walk.iter().position(|&b| b == body) {
+            let mut cycle = walk.split_off(at);
+            for &b in &cycle {
+                in_cycle[b] = true;
+            }
+            cycle.reverse();
+            let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+            cycle.rotate_left(first);
+            problems.push(Problem::Cycle(owned(&cycle)));
+        }
+    }
+    // Where each body's walk up first meets a cycle, worked out once per
+    // body however long the chains hanging off it are.
+    let mut meets: Vec<Option<usize>> = (0..names.len())
+        .map(|b| if in_cycle[b] { Some(b) } else { None })
+        .collect();
+    for start in 0..names.len() {
+        if reached[start] || meets[start].is_some() {
+            continue;
+        }
+        let mut walk = Vec::new();
+        let mut body = start;
+        while meets[body].is_none() {
+            walk.push(body);
+            body = parent[body].unwrap();
+        }
+        let cycle = meets[body];
+        for b in walk {
+            meets[b] = cycle;
+        }
+    }
+    for body in 0..names.len() {
+        if let (false, false, Some(cycle)) = (reached[body], in_cycle[body], meets[body]) {
+            problems.push(Problem::Orphaned {
+                body: names[body].to_string(),
+                cycle: names[cycle].to_string(),
+            });
+        }
+    }
+
+    Report { root, problems }
+}
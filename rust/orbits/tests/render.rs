@@ -0,0 +1,81 @@
+use orbits::{render, Tree};
+
+const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n";
+
+#[test]
+fn sizes() {
+    let tree = Tree::parse(EXAMPLE).unwrap();
+    let sizes = tree.sizes();
+    assert_eq!(sizes[tree.root()], 12);
+    assert_eq!(sizes[tree.body("D").unwrap()], 7);
+    assert_eq!(sizes[tree.body("L").unwrap()], 1);
+}
+
+#[test]
+fn text() {
+    let tree = Tree::parse(EXAMPLE).unwrap();
+    assert_eq!(
+        render::text(&tree, false),
+        "COM (12)\n\
+         \x20 B (11)\n\
+         \x20   C (8)\n\
+         \x20     D (7)\n\
+         \x20       E (5)\n\
+         \x20         F (1)\n\
+         \x20         J (3)\n\
+         \x20           K (2)\n\
+         \x20             L (1)\n\
+         \x20       I (1)\n\
+         \x20   G (2)\n\
+         \x20     H (1)\n"
+    );
+}
+
+#[test]
+fn folded_text() {
+    let tree = Tree::parse(EXAMPLE).unwrap();
+    assert_eq!(
+        render::text(&tree, true),
+        "COM)B (12)\n\
+         \x20 C)D (8)\n\
+         \x20   E (5)\n\
+         \x20     F (1)\n\
+         \x20     J)K)L (3)\n\
+         \x20   I (1)\n\
+         \x20 G)H (2)\n"
+    );
+}
+
+#[test]
+fn dot() {
+    let tree = Tree::parse("COM)B\nB)C\nB)D\nC)E\n").unwrap();
+    assert_eq!(
+        render::dot(&tree, None),
+        "digraph orbits {\n    \
+         node [shape=circle];\n    \
+         \"COM\" -> \"B\";\n    \
+         \"B\" -> \"C\";\n    \
+         \"B\" -> \"D\";\n    \
+         \"C\" -> \"E\";\n\
+         }\n"
+    );
+
+    let (e, d) = (tree.body("E").unwrap(), tree.body("D").unwrap());
+    let dot = render::dot(&tree, Some((e, d)));
+    for line in &[
+        "\"E\" [style=filled, fillcolor=gold];",
+        "\"B\" [style=filled, fillcolor=gold];",
+        "\"C\" -> \"E\" [color=red, penwidth=3];",
+        "\"B\" -> \"D\" [color=red, penwidth=3];",
+        "\"COM\" -> \"B\";",
+    ] {
+        assert!(dot.contains(line), "{} isn't in\n{}", line, dot);
+    }
+    assert!(!dot.contains("\"COM\" [style"));
+}
+
+#[test]
+fn quoting() {
+    let tree = Tree::parse("A\"B)C\\D\n").unwrap();
+    assert!(render::dot(&tree, None).contains("\"A\\\"B\" -> \"C\\\\D\";"));
+}
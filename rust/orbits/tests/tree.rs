@@ -0,0 +1,59 @@
+use orbits::Tree;
+
+/// The example from day 6, with YOU and SAN from part two.
+const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN\n";
+
+fn names(tree: &Tree, bodies: &[usize]) -> Vec<String> {
+    bodies.iter().map(|&b| tree.name(b).to_string()).collect()
+}
+
+#[test]
+fn counts_orbits() {
+    let tree = Tree::parse(&EXAMPLE.replace("K)YOU\nI)SAN\n", "")).unwrap();
+    assert_eq!(tree.len(), 12);
+    assert_eq!(tree.name(tree.root()), "COM");
+    assert_eq!(tree.depth(tree.body("L").unwrap()), 7);
+    assert_eq!(tree.total_orbits(), 42);
+}
+
+#[test]
+fn common_ancestors() {
+    let tree = Tree::parse(EXAMPLE).unwrap();
+    let body = |name| tree.body(name).unwrap();
+
+    assert_eq!(tree.name(tree.lca(body("YOU"), body("SAN"))), "D");
+    assert_eq!(tree.name(tree.lca(body("H"), body("L"))), "B");
+    assert_eq!(tree.name(tree.lca(body("E"), body("L"))), "E");
+    assert_eq!(tree.lca(body("F"), body("F")), body("F"));
+    assert_eq!(tree.distance(body("H"), body("F")), 6);
+}
+
+#[test]
+fn paths() {
+    let tree = Tree::parse(EXAMPLE).unwrap();
+    let body = |name| tree.body(name).unwrap();
+
+    let path = tree.path(body("YOU"), body("SAN"));
+    assert_eq!(
+        names(&tree, &path),
+        vec!["YOU", "K", "J", "E", "D", "I", "SAN"]
+    );
+    assert_eq!(
+        names(&tree, &tree.path(body("C"), body("E"))),
+        vec!["C", "D", "E"]
+    );
+    assert_eq!(tree.transfers(body("YOU"), body("SAN")), Some(4));
+    assert_eq!(tree.transfers(body("COM"), body("SAN")), None);
+}
+
+#[test]
+fn bad_maps() {
+    assert_eq!(
+        Tree::parse("COM)A\nA-B\n").unwrap_err(),
+        "line 2: expected A)B: A-B"
+    );
+    assert_eq!(
+        Tree::parse("COM)A\nCOM)B\nA)C\nB)C\nD)E\nE)D\n").unwrap_err(),
+        "C orbits A and B\norbits go round in a cycle: D)E)D"
+    );
+}
@@ -0,0 +1,121 @@
+use orbits::{parse, validate, Problem, Report, Tree};
+
+fn check(text: &str, required: &[&str]) -> Report {
+    validate(&parse(text).unwrap(), required)
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn good_maps() {
+    let report = check("B)C\nCOM)B\nB)D\n", &["D"]);
+    assert!(report.is_ok());
+    assert_eq!(report.root, Some("COM".to_string()));
+
+    // The root doesn't have to be called COM.
+    let tree = Tree::parse("SUN)EARTH\nEARTH)MOON\n").unwrap();
+    assert_eq!(tree.name(tree.root()), "SUN");
+    assert_eq!(tree.total_orbits(), 3);
+}
+
+#[test]
+fn missing_bodies() {
+    let report = check("COM)YOU\n", &["YOU", "SAN"]);
+    assert_eq!(report.problems, vec![Problem::Missing("SAN".to_string())]);
+    assert_eq!(report.to_string(), "no body called SAN");
+}
+
+#[test]
+fn two_parents() {
+    let report = check("COM)A\nCOM)B\nA)C\nB)C\nA)C\n", &[]);
+    assert_eq!(
+        report.problems,
+        vec![Problem::TwoParents {
+            body: "C".to_string(),
+            parents: names(&["A", "B"]),
+        }]
+    );
+    assert_eq!(report.to_string(), "C orbits A and B");
+}
+
+#[test]
+fn multiple_roots() {
+    // X's tree is bigger, so it's taken as the root.
+    let report = check("COM)A\nX)B\nB)C\n", &[]);
+    assert_eq!(report.root, Some("X".to_string()));
+    assert_eq!(
+        report.problems,
+        vec![Problem::MultipleRoots(names(&["COM", "X"]))]
+    );
+    assert_eq!(report.to_string(), "more than one root: COM, X");
+
+    // Giving X something to orbit leaves one root, but X has two parents.
+    let report = check("COM)A\nX)B\nA)C\nC)X\nA)X\n", &[]);
+    assert_eq!(report.to_string(), "X orbits C and A");
+
+    // The first one wins a tie.
+    assert_eq!(check("COM)A\nX)B\n", &[]).root, Some("COM".to_string()));
+}
+
+#[test]
+fn cycles() {
+    let report = check("COM)A\nC)B\nB)D\nD)C\nD)E\nE)F\nG)G\n", &[]);
+    assert_eq!(report.root, Some("COM".to_string()));
+    assert_eq!(
+        report.problems,
+        vec![
+            Problem::Cycle(names(&["C", "B", "D"])),
+            Problem::Cycle(names(&["G"])),
+            Problem::Orphaned {
+                body: "E".to_string(),
+                cycle: "D".to_string(),
+            },
+            // Further down counts too.
+            Problem::Orphaned {
+                body: "F".to_string(),
+                cycle: "D".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        report.to_string(),
+        "orbits go round in a cycle: C)B)D)C\n\
+         orbits go round in a cycle: G)G\n\
+         E never gets to the root, as D is caught in a cycle\n\
+         F never gets to the root, as D is caught in a cycle"
+    );
+}
+
+#[test]
+fn no_root() {
+    let report = check("A)B\nB)A\n", &[]);
+    assert_eq!(report.root, None);
+    assert_eq!(report.problems[0], Problem::NoRoot);
+    assert_eq!(check("", &[]).problems, vec![Problem::NoRoot]);
+    assert!(Tree::parse("").is_err());
+}
+
+#[test]
+fn problems_come_a_kind_at_a_time() {
+    // The orphan and the cycle come first in the map but last in the
+    // report.
+    let report = check("P)Q\nQ)P\nP)O\nCOM)A\nX)Y\nA)C\nY)C\n", &["Z"]);
+    assert_eq!(
+        report.problems,
+        vec![
+            Problem::Missing("Z".to_string()),
+            Problem::TwoParents {
+                body: "C".to_string(),
+                parents: names(&["A", "Y"]),
+            },
+            Problem::MultipleRoots(names(&["COM", "X"])),
+            Problem::Cycle(names(&["P", "Q"])),
+            Problem::Orphaned {
+                body: "O".to_string(),
+                cycle: "P".to_string(),
+            },
+        ]
+    );
+}
@@ -0,0 +1,19 @@
+//! Day 4's password hunt: counting the numbers in a range whose digits
+//! follow a set of rules.
+
+pub mod rule;
+pub mod search;
+
+pub use crate::rule::Rule;
+pub use crate::search::Search;
+
+/// `value`'s digits, most significant first, padded with zeros to
+/// `length`. Anything past `length` digits is dropped from the front.
+pub fn digits(mut value: u64, length: usize) -> Vec<u8> {
+    let mut digits = vec![0; length];
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % 10) as u8;
+        value /= 10;
+    }
+    digits
+}
@@ -0,0 +1,108 @@
+//! Rules about a password's digits, which can be put together into
+//! bigger ones.
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Each digit is at least the one before it.
+    NonDecreasing,
+    /// Some digit is the same as the one after it.
+    Pair,
+    /// Some run of the same digit is exactly two long, not part of a
+    /// longer one.
+    ExactPair,
+    /// The digits add up to this.
+    DigitSum(u32),
+    /// None of these digits appear.
+    Forbidden(Vec<u8>),
+    Not(Box<Rule>),
+    /// At least one of these holds.
+    Any(Vec<Rule>),
+    /// All of these hold.
+    All(Vec<Rule>),
+}
+
+impl Rule {
+    pub fn check(&self, digits: &[u8]) -> bool {
+        match self {
+            Rule::NonDecreasing => digits.windows(2).all(|w| w[0] <= w[1]),
+            Rule::Pair => digits.windows(2).any(|w| w[0] == w[1]),
+            Rule::ExactPair => runs(digits).any(|run| run == 2),
+            Rule::DigitSum(sum) => digits.iter().map(|&d| u32::from(d)).sum::<u32>() == *sum,
+            Rule::Forbidden(forbidden) => !digits.iter().any(|d| forbidden.contains(d)),
+            Rule::Not(rule) => !rule.check(digits),
+            Rule::Any(rules) => rules.iter().any(|r| r.check(digits)),
+            Rule::All(rules) => rules.iter().all(|r| r.check(digits)),
+        }
+    }
+
+    /// Reads a rule as `Display` writes it: `non-decreasing`, `pair`,
+    /// `exact-pair`, `sum=N`, `forbid=DIGITS`, or any of those after `!`
+    /// to turn it around. `Any` and `All` have no spelling.
+    pub fn parse(text: &str) -> Result<Rule, String> {
+        let text = text.trim();
+        if let Some(rest) = text.strip_prefix('!') {
+            return Ok(Rule::Not(Box::new(Rule::parse(rest)?)));
+        }
+
+        match text {
+            "non-decreasing" => return Ok(Rule::NonDecreasing),
+            "pair" => return Ok(Rule::Pair),
+            "exact-pair" => return Ok(Rule::ExactPair),
+            _ => (),
+        }
+
+        if let Some(sum) = text.strip_prefix("sum=") {
+            return sum
+                .parse()
+                .map(Rule::DigitSum)
+                .map_err(|_| format!("bad digit sum: {}", sum));
+        }
+        if let Some(digits) = text.strip_prefix("forbid=") {
+            return digits
+                .chars()
+                .map(|c| c.to_digit(10).map(|d| d as u8))
+                .collect::<Option<Vec<u8>>>()
+                .filter(|digits| !digits.is_empty())
+                .map(Rule::Forbidden)
+                .ok_or_else(|| format!("bad forbidden digits: {}", digits));
+        }
+
+        Err(format!("unknown rule: {}", text))
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, name: &str, rules: &[Rule]| {
+            let rules: Vec<String> = rules.iter().map(|r| r.to_string()).collect();
+            write!(f, "{}({})", name, rules.join(", "))
+        };
+
+        match self {
+            Rule::NonDecreasing => write!(f, "non-decreasing"),
+            Rule::Pair => write!(f, "pair"),
+            Rule::ExactPair => write!(f, "exact-pair"),
+            Rule::DigitSum(sum) => write!(f, "sum={}", sum),
+            Rule::Forbidden(digits) => {
+                write!(f, "forbid=")?;
+                digits.iter().try_for_each(|d| write!(f, "{}", d))
+            }
+            Rule::Not(rule) => write!(f, "!{}", rule),
+            Rule::Any(rules) => list(f, "any", rules),
+            Rule::All(rules) => list(f, "all", rules),
+        }
+    }
+}
+
+/// How long each run of the same digit is, in order.
+fn runs(digits: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    let mut rest = digits;
+    std::iter::from_fn(move || {
+        let first = *rest.first()?;
+        let run = rest.iter().take_while(|&&d| d == first).count();
+        rest = &rest[run..];
+        Some(run)
+    })
+}
@@ -0,0 +1,118 @@
+//! Running the rules over a range of numbers.
+
+use crate::digits;
+use crate::rule::Rule;
+
+/// Every number from `start` to `end`, both included, that follows all
+/// the rules when written out as `length` digits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Search {
+    start: u64,
+    end: u64,
+    length: usize,
+    rules: Vec<Rule>,
+}
+
+impl Search {
+    /// As long as `end` is, with no rules yet.
+    pub fn new(start: u64, end: u64) -> Self {
+        Search {
+            start,
+            end,
+            length: end.to_string().len(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Reads a range like `231832-767346`.
+    pub fn parse(range: &str) -> Result<Self, String> {
+        let bad = || format!("expected START-END: {}", range);
+        let mut ends = range.trim().splitn(2, '-');
+        let start: u64 = ends.next().unwrap().parse().map_err(|_| bad())?;
+        let end: u64 = ends.next().ok_or_else(bad)?.parse().map_err(|_| bad())?;
+        if start > end {
+            return Err(format!("range runs backwards: {}", range));
+        }
+        Ok(Search::new(start, end))
+    }
+
+    /// Pads shorter numbers out with leading zeros, and leaves out any
+    /// that need more digits than this.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn rules<I: IntoIterator<Item = Rule>>(mut self, rules: I) -> Self {
+        self.rules.extend(rules);
+        self
+    }
+
+    pub fn check(&self, value: u64) -> bool {
+        let digits = digits(value, self.length);
+        self.rules.iter().all(|r| r.check(&digits))
+    }
+
+    /// Every number that passes, in order.
+    ///
+    /// When `NonDecreasing` is one of the rules, a number that drops
+    /// somewhere skips straight to the next one that doesn't drop there,
+    /// which saves going through most of the range one by one.
+    pub fn matches(&self) -> Matches<'_> {
+        // The first number too long to fit.
+        let limit = 10u64.checked_pow(self.length as u32);
+        Matches {
+            search: self,
+            next: Some(self.start),
+            end: limit.map_or(self.end, |limit| self.end.min(limit.saturating_sub(1))),
+            skip: self.rules.contains(&Rule::NonDecreasing),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.matches().count()
+    }
+}
+
+pub struct Matches<'a> {
+    search: &'a Search,
+    next: Option<u64>,
+    end: u64,
+    skip: bool,
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while let Some(value) = self.next.filter(|&v| v <= self.end) {
+            let mut digits = digits(value, self.search.length);
+
+            if self.skip {
+                if let Some(i) = (1..digits.len()).find(|&i| digits[i] < digits[i - 1]) {
+                    // Everything from the drop on becomes the digit before
+                    // it, the smallest way to stop it dropping.
+                    let before = digits[i - 1];
+                    digits[i..].iter_mut().for_each(|d| *d = before);
+                    self.next = digits
+                        .iter()
+                        .try_fold(0u64, |v, &d| v.checked_mul(10)?.checked_add(u64::from(d)));
+                    continue;
+                }
+            }
+
+            self.next = value.checked_add(1);
+            if self.search.rules.iter().all(|r| r.check(&digits)) {
+                return Some(value);
+            }
+        }
+
+        self.next = None;
+        None
+    }
+}
@@ -0,0 +1,74 @@
+use passwords::{digits, Rule};
+
+fn check(rule: &Rule, value: u64) -> bool {
+    rule.check(&digits(value, 6))
+}
+
+#[test]
+fn day4_examples() {
+    let part1 = Rule::All(vec![Rule::NonDecreasing, Rule::Pair]);
+    assert!(check(&part1, 111111));
+    assert!(!check(&part1, 223450));
+    assert!(!check(&part1, 123789));
+
+    let part2 = Rule::All(vec![Rule::NonDecreasing, Rule::ExactPair]);
+    assert!(check(&part2, 112233));
+    assert!(!check(&part2, 123444));
+    assert!(check(&part2, 111122));
+}
+
+#[test]
+fn other_rules() {
+    assert!(check(&Rule::DigitSum(21), 123456));
+    assert!(!check(&Rule::DigitSum(20), 123456));
+    assert!(check(&Rule::Forbidden(vec![0, 9]), 123456));
+    assert!(!check(&Rule::Forbidden(vec![0, 9]), 123459));
+    assert!(check(&Rule::Not(Box::new(Rule::Pair)), 123456));
+    assert!(check(
+        &Rule::Any(vec![Rule::Pair, Rule::DigitSum(21)]),
+        123456
+    ));
+    assert!(!check(&Rule::Any(vec![]), 123456));
+
+    // Leading zeros count as digits.
+    assert_eq!(digits(42, 4), vec![0, 0, 4, 2]);
+    assert!(check(&Rule::Pair, 42));
+}
+
+#[test]
+fn parsing() {
+    for text in &[
+        "non-decreasing",
+        "pair",
+        "exact-pair",
+        "sum=30",
+        "forbid=079",
+        "!pair",
+        "!!exact-pair",
+    ] {
+        let rule = Rule::parse(text).unwrap();
+        assert_eq!(rule.to_string(), *text);
+    }
+    assert_eq!(
+        Rule::parse(" !sum=4 "),
+        Ok(Rule::Not(Box::new(Rule::DigitSum(4))))
+    );
+    assert_eq!(
+        Rule::All(vec![Rule::Pair, Rule::Any(vec![Rule::DigitSum(3)])]).to_string(),
+        "all(pair, any(sum=3))"
+    );
+
+    assert_eq!(
+        Rule::parse("triple"),
+        Err("unknown rule: triple".to_string())
+    );
+    assert_eq!(Rule::parse("sum=x"), Err("bad digit sum: x".to_string()));
+    assert_eq!(
+        Rule::parse("forbid=1a"),
+        Err("bad forbidden digits: 1a".to_string())
+    );
+    assert_eq!(
+        Rule::parse("forbid="),
+        Err("bad forbidden digits: ".to_string())
+    );
+}
@@ -0,0 +1,56 @@
+use passwords::{Rule, Search};
+
+#[test]
+fn skipping_matches_brute_force() {
+    let rules = vec![
+        vec![Rule::NonDecreasing],
+        vec![Rule::NonDecreasing, Rule::ExactPair],
+        vec![Rule::Pair, Rule::NonDecreasing, Rule::DigitSum(12)],
+        vec![Rule::NonDecreasing, Rule::Forbidden(vec![5])],
+    ];
+    for rules in rules {
+        let search = Search::new(1032, 48213).length(5).rules(rules);
+        let brute: Vec<u64> = (1032..=48213).filter(|&v| search.check(v)).collect();
+        assert_eq!(search.matches().collect::<Vec<_>>(), brute);
+    }
+}
+
+#[test]
+fn ranges() {
+    let search = Search::parse("231832-767346").unwrap();
+    assert_eq!(
+        search.rule(Rule::NonDecreasing).rule(Rule::Pair).count(),
+        1330
+    );
+
+    // Both ends are in.
+    let search = Search::parse("11-22").unwrap().rule(Rule::Pair);
+    assert_eq!(search.matches().collect::<Vec<_>>(), vec![11, 22]);
+
+    assert_eq!(
+        Search::parse("5-1"),
+        Err("range runs backwards: 5-1".to_string())
+    );
+    assert_eq!(Search::parse("5"), Err("expected START-END: 5".to_string()));
+    assert_eq!(
+        Search::parse("a-7"),
+        Err("expected START-END: a-7".to_string())
+    );
+}
+
+#[test]
+fn lengths() {
+    // Padded with zeros, so 7 is 007 and non-decreasing.
+    let search = Search::new(0, 20).length(3).rule(Rule::NonDecreasing);
+    assert_eq!(search.count(), 21 - 2);
+
+    // Too long to fit in two digits.
+    let search = Search::new(90, 150).length(2);
+    assert_eq!(search.matches().last(), Some(99));
+
+    // Near the top of u64, skipping can't overflow.
+    let search = Search::new(u64::MAX - 10, u64::MAX)
+        .length(20)
+        .rule(Rule::NonDecreasing);
+    assert_eq!(search.count(), 0);
+}
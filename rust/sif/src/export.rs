@@ -0,0 +1,186 @@
+//! Writing a layer out as text, terminal art or an image file. Anything
+//! that isn't black or white is drawn as transparent.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::image::Layer;
+use crate::{BLACK, WHITE};
+
+pub type Colour = [u8; 3];
+
+/// What colour each kind of pixel is drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub black: Colour,
+    pub white: Colour,
+    pub transparent: Colour,
+    /// Pixels worth pointing out, like the ones a layer has just fixed.
+    pub highlight: Colour,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            black: [0, 0, 0],
+            white: [255, 255, 255],
+            transparent: [128, 128, 128],
+            highlight: [220, 40, 40],
+        }
+    }
+}
+
+impl Palette {
+    /// Reads `black,white[,transparent[,highlight]]` as hex colours like
+    /// `1e1e2e`. Whatever isn't given keeps its default.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let colours = text
+            .split(',')
+            .map(|c| {
+                let c = c.trim().trim_start_matches('#');
+                let channel = |i: usize| {
+                    c.get(i..i + 2)
+                        .and_then(|h| u8::from_str_radix(h, 16).ok())
+                        .ok_or_else(|| format!("bad colour {:?}", c))
+                };
+                if c.len() != 6 {
+                    return Err(format!("bad colour {:?}", c));
+                }
+                Ok([channel(0)?, channel(2)?, channel(4)?])
+            })
+            .collect::<Result<Vec<Colour>, String>>()?;
+
+        if colours.len() < 2 || colours.len() > 4 {
+            return Err(format!("expected two to four colours: {}", text));
+        }
+
+        let mut palette = Palette::default();
+        let mut slots = [
+            &mut palette.black,
+            &mut palette.white,
+            &mut palette.transparent,
+            &mut palette.highlight,
+        ];
+        for (slot, colour) in slots.iter_mut().zip(colours) {
+            **slot = colour;
+        }
+        Ok(palette)
+    }
+
+    pub fn colour(&self, pixel: u8) -> Colour {
+        match pixel {
+            BLACK => self.black,
+            WHITE => self.white,
+            _ => self.transparent,
+        }
+    }
+
+    /// How bright each colour is, for greyscale.
+    fn grey(&self, pixel: u8) -> u8 {
+        let [r, g, b] = self.colour(pixel);
+        ((u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000) as u8
+    }
+}
+
+/// The layer's pixels with each one blown up to `scale` pixels square,
+/// row by row.
+fn scaled(layer: &Layer<'_>, scale: usize) -> Vec<Vec<u8>> {
+    layer
+        .rows()
+        .flat_map(|row| {
+            let row: Vec<u8> = row
+                .iter()
+                .flat_map(|&p| std::iter::repeat_n(p, scale))
+                .collect();
+            std::iter::repeat_n(row, scale)
+        })
+        .collect()
+}
+
+/// White pixels as `#` on a blank background, so the letters read the
+/// right way round.
+pub fn text(layer: &Layer<'_>) -> String {
+    let mut text = String::new();
+    for row in layer.rows() {
+        let line: String = row
+            .iter()
+            .map(|&p| if p == WHITE { '#' } else { ' ' })
+            .collect();
+        writeln!(text, "{}", line.trim_end()).unwrap();
+    }
+    text
+}
+
+/// Like `text`, but with half blocks so each line holds two rows of
+/// pixels and they come out closer to square.
+pub fn blocks(layer: &Layer<'_>, scale: usize) -> String {
+    let rows = scaled(layer, scale);
+    let mut text = String::new();
+
+    for pair in rows.chunks(2) {
+        let blank = vec![BLACK; pair[0].len()];
+        let (top, bottom) = (&pair[0], pair.get(1).unwrap_or(&blank));
+        let line: String = top
+            .iter()
+            .zip(bottom)
+            .map(|(&t, &b)| match (t == WHITE, b == WHITE) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            })
+            .collect();
+        writeln!(text, "{}", line.trim_end()).unwrap();
+    }
+
+    text
+}
+
+/// A plain PBM, where black is 1. Transparent pixels come out white.
+pub fn pbm(layer: &Layer<'_>, scale: usize) -> String {
+    let rows = scaled(layer, scale);
+    let mut pbm = format!("P1\n{} {}\n", layer.width() * scale, rows.len());
+
+    for row in rows {
+        let bits: Vec<&str> = row
+            .iter()
+            .map(|&p| if p == BLACK { "1" } else { "0" })
+            .collect();
+        writeln!(pbm, "{}", bits.join(" ")).unwrap();
+    }
+
+    pbm
+}
+
+/// A plain PGM, each pixel as bright as its colour in `palette`.
+pub fn pgm(layer: &Layer<'_>, scale: usize, palette: &Palette) -> String {
+    let rows = scaled(layer, scale);
+    let mut pgm = format!("P2\n{} {}\n255\n", layer.width() * scale, rows.len());
+
+    for row in rows {
+        let greys: Vec<String> = row.iter().map(|&p| palette.grey(p).to_string()).collect();
+        writeln!(pgm, "{}", greys.join(" ")).unwrap();
+    }
+
+    pgm
+}
+
+pub fn png<W: Write>(
+    layer: &Layer<'_>,
+    scale: usize,
+    palette: &Palette,
+    output: W,
+) -> io::Result<()> {
+    let rows = scaled(layer, scale);
+    let mut encoder = png::Encoder::new(output, (layer.width() * scale) as u32, rows.len() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let pixels: Vec<u8> = rows
+        .iter()
+        .flatten()
+        .flat_map(|&p| palette.colour(p).to_vec())
+        .collect();
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&pixels).map_err(io::Error::other)
+}
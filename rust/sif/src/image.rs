@@ -0,0 +1,198 @@
+//! Decoding SIF data into layers, and stacking them up.
+
+use std::error;
+use std::fmt;
+
+use crate::TRANSPARENT;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A width or height of zero, or a layer too big to count.
+    BadDimensions { width: usize, height: usize },
+    /// Something other than a digit, counting positions from 1.
+    BadDigit { position: usize, found: char },
+    /// No data at all.
+    Empty,
+    /// Data that stops partway through a layer.
+    Truncated { len: usize, layer_size: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BadDimensions { width, height } => {
+                write!(f, "bad dimensions {}x{}", width, height)
+            }
+            Error::BadDigit { position, found } => {
+                write!(
+                    f,
+                    "position {}: expected a digit, found {:?}",
+                    position, found
+                )
+            }
+            Error::Empty => write!(f, "no image data"),
+            Error::Truncated { len, layer_size } => write!(
+                f,
+                "{} digits isn't a whole number of {}-digit layers",
+                len, layer_size
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// A decoded image, every layer the same size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    pub(crate) fn new(width: usize, height: usize, pixels: Vec<u8>) -> Result<Self, Error> {
+        let layer_size = match width.checked_mul(height) {
+            Some(size) if size > 0 => size,
+            _ => return Err(Error::BadDimensions { width, height }),
+        };
+        if pixels.is_empty() {
+            return Err(Error::Empty);
+        }
+        if !pixels.len().is_multiple_of(layer_size) {
+            return Err(Error::Truncated {
+                len: pixels.len(),
+                layer_size,
+            });
+        }
+
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Reads the digits in the order they come, each layer's rows top to
+    /// bottom, ignoring whitespace at either end. Pixels are kept as they
+    /// are, so any digit is fine.
+    pub fn parse(text: &str, width: usize, height: usize) -> Result<Self, Error> {
+        let offset = text.chars().take_while(|c| c.is_whitespace()).count();
+        let text = text.trim();
+        let mut pixels = Vec::with_capacity(text.len());
+
+        for (n, c) in text.chars().enumerate() {
+            match c.to_digit(10) {
+                Some(d) => pixels.push(d as u8),
+                None => {
+                    return Err(Error::BadDigit {
+                        position: offset + n + 1,
+                        found: c,
+                    })
+                }
+            }
+        }
+
+        Image::new(width, height, pixels)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn layer_size(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// How many layers there are, never none.
+    pub fn depth(&self) -> usize {
+        self.pixels.len() / self.layer_size()
+    }
+
+    pub fn layer(&self, n: usize) -> Option<Layer<'_>> {
+        let size = self.layer_size();
+        self.pixels
+            .get(n * size..(n + 1) * size)
+            .map(|pixels| Layer {
+                width: self.width,
+                pixels,
+            })
+    }
+
+    /// Front to back.
+    pub fn layers(&self) -> impl Iterator<Item = Layer<'_>> {
+        let width = self.width;
+        self.pixels
+            .chunks(self.layer_size())
+            .map(move |pixels| Layer { width, pixels })
+    }
+
+    /// The layers stacked up into one, each pixel the first one that
+    /// isn't transparent from the front. A pixel transparent all the way
+    /// down stays transparent.
+    pub fn composite(&self) -> Image {
+        let mut pixels = vec![TRANSPARENT; self.layer_size()];
+        for layer in self.layers().collect::<Vec<_>>().into_iter().rev() {
+            for (pixel, &p) in pixels.iter_mut().zip(layer.pixels) {
+                if p != TRANSPARENT {
+                    *pixel = p;
+                }
+            }
+        }
+
+        Image {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
+
+/// One layer's worth of pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layer<'a> {
+    width: usize,
+    pixels: &'a [u8],
+}
+
+impl<'a> Layer<'a> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.len() / self.width
+    }
+
+    /// Panics outside the layer.
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        assert!(x < self.width, "x {} is outside the layer", x);
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn pixels(&self) -> &'a [u8] {
+        self.pixels
+    }
+
+    /// Top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.pixels.chunks(self.width)
+    }
+
+    /// How many of each digit there are.
+    pub fn histogram(&self) -> [usize; 10] {
+        let mut counts = [0; 10];
+        for &p in self.pixels {
+            counts[p as usize] += 1;
+        }
+        counts
+    }
+
+    pub fn count(&self, digit: u8) -> usize {
+        self.pixels.iter().filter(|&&p| p == digit).count()
+    }
+}
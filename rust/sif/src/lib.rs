@@ -0,0 +1,14 @@
+//! Day 8's Space Image Format: a run of digits, one per pixel, split into
+//! layers of `width` × `height` that stack up into a picture.
+
+pub mod export;
+pub mod image;
+pub mod steps;
+
+pub use crate::export::Palette;
+pub use crate::image::{Error, Image, Layer};
+
+pub const BLACK: u8 = 0;
+pub const WHITE: u8 = 1;
+/// Lets whatever's on the layer below show through.
+pub const TRANSPARENT: u8 = 2;
@@ -0,0 +1,235 @@
+//! How the picture builds up as the layers go on, from the front: the
+//! composite after each layer, and which pixels that layer settled.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::export::{Colour, Palette};
+use crate::image::{Image, Layer};
+use crate::{BLACK, TRANSPARENT, WHITE};
+
+/// The picture once layers `0..=layer` are stacked up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub layer: usize,
+    /// A single layer, transparent wherever nothing's shown yet.
+    pub composite: Image,
+    /// Pixels transparent until this layer and not since.
+    pub fixed: Vec<bool>,
+}
+
+impl Step {
+    pub fn composite(&self) -> Layer<'_> {
+        self.composite.layer(0).unwrap()
+    }
+
+    /// How many pixels this layer fixed.
+    pub fn fixed_count(&self) -> usize {
+        self.fixed.iter().filter(|&&f| f).count()
+    }
+
+    /// How many pixels are still waiting on a layer further back.
+    pub fn transparent(&self) -> usize {
+        self.composite().count(TRANSPARENT)
+    }
+
+    /// Index into `colours` for each pixel: black, white, transparent,
+    /// then highlight for the ones this layer fixed.
+    fn indices(&self, scale: usize) -> Vec<u8> {
+        let layer = self.composite();
+        let mut indices = Vec::with_capacity(layer.pixels().len() * scale * scale);
+        for y in 0..layer.height() * scale {
+            for x in 0..layer.width() * scale {
+                let (x, y) = (x / scale, y / scale);
+                indices.push(if self.fixed[y * layer.width() + x] {
+                    3
+                } else {
+                    match layer.get(x, y) {
+                        BLACK => 0,
+                        WHITE => 1,
+                        _ => 2,
+                    }
+                });
+            }
+        }
+        indices
+    }
+}
+
+/// One step per layer, front to back.
+pub fn steps(image: &Image) -> Vec<Step> {
+    let size = image.width() * image.height();
+    let mut pixels = vec![TRANSPARENT; size];
+    let mut steps = Vec::with_capacity(image.depth());
+
+    for (n, layer) in image.layers().enumerate() {
+        let mut fixed = vec![false; size];
+        for (i, &p) in layer.pixels().iter().enumerate() {
+            if pixels[i] == TRANSPARENT && p != TRANSPARENT {
+                pixels[i] = p;
+                fixed[i] = true;
+            }
+        }
+
+        steps.push(Step {
+            layer: n,
+            composite: Image::new(image.width(), image.height(), pixels.clone()).unwrap(),
+            fixed,
+        });
+    }
+
+    steps
+}
+
+/// The colours `Step::indices` picks from.
+fn colours(palette: &Palette) -> [Colour; 4] {
+    [
+        palette.black,
+        palette.white,
+        palette.transparent,
+        palette.highlight,
+    ]
+}
+
+fn flat(palette: &Palette) -> Vec<u8> {
+    colours(palette).iter().flatten().cloned().collect()
+}
+
+/// A line saying what the step did, then the picture in colour for a
+/// terminal, two rows of pixels to a line.
+pub fn ansi(step: &Step, scale: usize, palette: &Palette) -> String {
+    let layer = step.composite();
+    let (width, height) = (layer.width() * scale, layer.height() * scale);
+    let indices = step.indices(scale);
+    let colours = colours(palette);
+
+    let mut text = format!(
+        "layer {}: {} fixed, {} still transparent\n",
+        step.layer,
+        step.fixed_count(),
+        step.transparent()
+    );
+    for y in (0..height).step_by(2) {
+        for x in 0..width {
+            let [r, g, b] = colours[indices[y * width + x] as usize];
+            write!(text, "\x1b[38;2;{};{};{}m", r, g, b).unwrap();
+            if y + 1 < height {
+                let [r, g, b] = colours[indices[(y + 1) * width + x] as usize];
+                write!(text, "\x1b[48;2;{};{};{}m", r, g, b).unwrap();
+            } else {
+                text.push_str("\x1b[49m");
+            }
+            text.push('▀');
+        }
+        text.push_str("\x1b[0m\n");
+    }
+
+    text
+}
+
+fn size(steps: &[Step], scale: usize) -> (usize, usize) {
+    let image = &steps[0].composite;
+    (image.width() * scale, image.height() * scale)
+}
+
+fn invalid<E: ToString>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+/// One still, highlights and all.
+pub fn png<W: Write>(step: &Step, scale: usize, palette: &Palette, output: W) -> io::Result<()> {
+    let layer = step.composite();
+    let mut encoder = png::Encoder::new(
+        output,
+        (layer.width() * scale) as u32,
+        (layer.height() * scale) as u32,
+    );
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(flat(palette));
+
+    let mut writer = encoder.write_header().map_err(invalid)?;
+    writer
+        .write_image_data(&step.indices(scale))
+        .map_err(invalid)
+}
+
+/// Every step as `layer-000.png` and so on in `dir`, which has to be
+/// there already.
+pub fn frames<P: AsRef<Path>>(
+    steps: &[Step],
+    scale: usize,
+    palette: &Palette,
+    dir: P,
+) -> io::Result<()> {
+    let digits = steps.len().saturating_sub(1).to_string().len().max(3);
+    for step in steps {
+        let path = dir.as_ref().join(format!(
+            "layer-{:0digits$}.png",
+            step.layer,
+            digits = digits
+        ));
+        png(step, scale, palette, File::create(path)?)?;
+    }
+    Ok(())
+}
+
+pub fn gif<W: Write>(
+    steps: &[Step],
+    delay: Duration,
+    scale: usize,
+    palette: &Palette,
+    output: W,
+) -> io::Result<()> {
+    if steps.is_empty() {
+        return Ok(());
+    }
+
+    let (width, height) = size(steps, scale);
+    let mut encoder =
+        gif::Encoder::new(output, width as u16, height as u16, &flat(palette)).map_err(invalid)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(invalid)?;
+
+    for step in steps {
+        let mut frame =
+            gif::Frame::from_indexed_pixels(width as u16, height as u16, step.indices(scale), None);
+        frame.delay = (delay.as_millis() / 10) as u16;
+        encoder.write_frame(&frame).map_err(invalid)?;
+    }
+    Ok(())
+}
+
+pub fn apng<W: Write>(
+    steps: &[Step],
+    delay: Duration,
+    scale: usize,
+    palette: &Palette,
+    output: W,
+) -> io::Result<()> {
+    if steps.is_empty() {
+        return Ok(());
+    }
+
+    let (width, height) = size(steps, scale);
+    let mut encoder = png::Encoder::new(output, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(flat(palette));
+    encoder
+        .set_animated(steps.len() as u32, 0)
+        .map_err(invalid)?;
+    encoder
+        .set_frame_delay(delay.as_millis() as u16, 1000)
+        .map_err(invalid)?;
+
+    let mut writer = encoder.write_header().map_err(invalid)?;
+    for step in steps {
+        writer
+            .write_image_data(&step.indices(scale))
+            .map_err(invalid)?;
+    }
+    writer.finish().map_err(invalid)
+}
@@ -0,0 +1,85 @@
+use sif::export::{self, Palette};
+use sif::Image;
+
+/// Black, white and transparent, then white, black and transparent.
+fn image() -> Image {
+    Image::parse("012102", 3, 2).unwrap()
+}
+
+#[test]
+fn text() {
+    let image = image();
+    let layer = image.layer(0).unwrap();
+    assert_eq!(export::text(&layer), " #\n#\n");
+}
+
+#[test]
+fn blocks() {
+    let image = Image::parse("011010100011", 4, 3).unwrap();
+    let layer = image.layer(0).unwrap();
+    assert_eq!(export::blocks(&layer, 1), "▄▀█\n  ▀▀\n");
+    assert_eq!(export::blocks(&layer, 2), "  ████\n██  ██\n    ████\n");
+}
+
+#[test]
+fn pbm() {
+    let image = image();
+    let layer = image.layer(0).unwrap();
+    assert_eq!(export::pbm(&layer, 1), "P1\n3 2\n1 0 0\n0 1 0\n");
+    assert_eq!(
+        export::pbm(&layer, 2),
+        "P1\n6 4\n1 1 0 0 0 0\n1 1 0 0 0 0\n0 0 1 1 0 0\n0 0 1 1 0 0\n"
+    );
+}
+
+#[test]
+fn pgm() {
+    let image = image();
+    let layer = image.layer(0).unwrap();
+    let palette = Palette::parse("000000,ffffff,ff0000").unwrap();
+    assert_eq!(
+        export::pgm(&layer, 1, &palette),
+        "P2\n3 2\n255\n0 255 76\n255 0 76\n"
+    );
+}
+
+#[test]
+fn png() {
+    let image = image();
+    let layer = image.layer(0).unwrap();
+    let palette = Palette::parse("#102030, 405060").unwrap();
+
+    let mut data = Vec::new();
+    export::png(&layer, 3, &palette, &mut data).unwrap();
+
+    let mut reader = png::Decoder::new(&data[..]).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!((info.width, info.height), (9, 6));
+    assert_eq!(&pixels[..3], &[0x10, 0x20, 0x30]);
+    assert_eq!(&pixels[9..12], &[0x40, 0x50, 0x60]);
+    assert_eq!(&pixels[18..21], &[128, 128, 128]);
+}
+
+#[test]
+fn palettes() {
+    assert_eq!(Palette::parse("000000,ffffff").unwrap(), Palette::default());
+    assert_eq!(
+        Palette::parse("000000,ffffff,808080,00ff00")
+            .unwrap()
+            .highlight,
+        [0, 255, 0]
+    );
+    assert_eq!(
+        Palette::parse("000000,fffff").unwrap_err(),
+        "bad colour \"fffff\""
+    );
+    assert_eq!(
+        Palette::parse("00000g,ffffff").unwrap_err(),
+        "bad colour \"00000g\""
+    );
+    assert_eq!(
+        Palette::parse("000000").unwrap_err(),
+        "expected two to four colours: 000000"
+    );
+}
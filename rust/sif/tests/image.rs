@@ -0,0 +1,84 @@
+use sif::{Error, Image, TRANSPARENT};
+
+#[test]
+fn layers() {
+    let image = Image::parse("123456789012\n", 3, 2).unwrap();
+    assert_eq!((image.width(), image.height(), image.depth()), (3, 2, 2));
+
+    let layer = image.layer(1).unwrap();
+    assert_eq!(layer.pixels(), &[7, 8, 9, 0, 1, 2]);
+    assert_eq!(layer.get(2, 0), 9);
+    assert_eq!(
+        layer.rows().collect::<Vec<_>>(),
+        vec![&[7, 8, 9], &[0, 1, 2]]
+    );
+    assert!(image.layer(2).is_none());
+    assert_eq!(image.layers().count(), 2);
+}
+
+#[test]
+fn histograms() {
+    let image = Image::parse("001229", 6, 1).unwrap();
+    let layer = image.layer(0).unwrap();
+    assert_eq!(layer.histogram(), [2, 1, 2, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(layer.count(2), 2);
+    assert_eq!(layer.count(5), 0);
+}
+
+#[test]
+fn composite() {
+    let image = Image::parse("0222112222120000", 2, 2).unwrap();
+    let composite = image.composite();
+    assert_eq!(composite.depth(), 1);
+    assert_eq!(composite.layer(0).unwrap().pixels(), &[0, 1, 1, 0]);
+
+    // Nothing to show through to.
+    let image = Image::parse("2212", 2, 1).unwrap();
+    assert_eq!(
+        image.composite().layer(0).unwrap().pixels(),
+        &[1, TRANSPARENT]
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        Image::parse(" \n1230", 0, 4),
+        Err(Error::BadDimensions {
+            width: 0,
+            height: 4
+        })
+    );
+    assert_eq!(
+        Image::parse("1", usize::MAX, 2),
+        Err(Error::BadDimensions {
+            width: usize::MAX,
+            height: 2
+        })
+    );
+
+    let e = Image::parse("  12x4", 2, 2).unwrap_err();
+    assert_eq!(
+        e,
+        Error::BadDigit {
+            position: 5,
+            found: 'x'
+        }
+    );
+    assert_eq!(e.to_string(), "position 5: expected a digit, found 'x'");
+
+    assert_eq!(Image::parse("\n", 2, 2), Err(Error::Empty));
+
+    let e = Image::parse("123456", 2, 2).unwrap_err();
+    assert_eq!(
+        e,
+        Error::Truncated {
+            len: 6,
+            layer_size: 4
+        }
+    );
+    assert_eq!(
+        e.to_string(),
+        "6 digits isn't a whole number of 4-digit layers"
+    );
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::time::Duration;
+
+use sif::steps::{self, Step};
+use sif::{Image, Palette, TRANSPARENT};
+
+fn example() -> Vec<Step> {
+    steps::steps(&Image::parse("0222112222120000", 2, 2).unwrap())
+}
+
+#[test]
+fn builds_up() {
+    let steps = example();
+    assert_eq!(steps.len(), 4);
+
+    let pixels: Vec<&[u8]> = steps.iter().map(|s| s.composite().pixels()).collect();
+    assert_eq!(
+        pixels,
+        vec![
+            &[0, TRANSPARENT, TRANSPARENT, TRANSPARENT][..],
+            &[0, 1, TRANSPARENT, TRANSPARENT],
+            &[0, 1, 1, TRANSPARENT],
+            &[0, 1, 1, 0],
+        ]
+    );
+    assert_eq!(steps[1].fixed, vec![false, true, false, false]);
+    assert_eq!(steps[3].fixed, vec![false, false, false, true]);
+    assert_eq!(
+        steps
+            .iter()
+            .map(|s| (s.layer, s.fixed_count(), s.transparent()))
+            .collect::<Vec<_>>(),
+        vec![(0, 1, 3), (1, 1, 2), (2, 1, 1), (3, 1, 0)]
+    );
+}
+
+#[test]
+fn ansi() {
+    let palette = Palette::parse("000000,ffffff,808080,ff0000").unwrap();
+    let text = steps::ansi(&example()[1], 1, &palette);
+    assert_eq!(
+        text,
+        "layer 1: 1 fixed, 2 still transparent\n\
+         \x1b[38;2;0;0;0m\x1b[48;2;128;128;128m▀\
+         \x1b[38;2;255;0;0m\x1b[48;2;128;128;128m▀\x1b[0m\n"
+    );
+
+    // An odd row out at the bottom has nothing behind it.
+    let steps = steps::steps(&Image::parse("101", 1, 3).unwrap());
+    let text = steps::ansi(&steps[0], 1, &palette);
+    assert!(text.ends_with("\x1b[38;2;255;0;0m\x1b[49m▀\x1b[0m\n"));
+}
+
+#[test]
+fn animations() {
+    let steps = example();
+    let palette = Palette::default();
+    let delay = Duration::from_millis(100);
+
+    let mut data = Vec::new();
+    steps::apng(&steps, delay, 2, &palette, &mut data).unwrap();
+    let reader = png::Decoder::new(&data[..]).read_info().unwrap();
+    let info = reader.info();
+    assert_eq!((info.width, info.height), (4, 4));
+    assert_eq!(info.animation_control.unwrap().num_frames, 4);
+
+    let mut data = Vec::new();
+    steps::gif(&steps, delay, 2, &palette, &mut data).unwrap();
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(&data[..]).unwrap();
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        frames.push(frame.buffer.to_vec());
+    }
+    assert_eq!(frames.len(), 4);
+    // Layer 1 fixed the top right, so it's highlighted.
+    assert_eq!(&frames[1][..4], &[0, 0, 3, 3]);
+    assert_eq!(&frames[3][..4], &[0, 0, 1, 1]);
+}
+
+#[test]
+fn frames() {
+    let dir = std::env::temp_dir().join(format!("sif-frames-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    steps::frames(&example(), 1, &Palette::default(), &dir).unwrap();
+
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        names,
+        vec![
+            "layer-000.png",
+            "layer-001.png",
+            "layer-002.png",
+            "layer-003.png"
+        ]
+    );
+}